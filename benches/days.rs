@@ -0,0 +1,51 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use aoc2021::PUZZLES;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use utils::Part;
+
+// criterion's own default
+const DEFAULT_SAMPLE_SIZE: usize = 100;
+
+// one group per day, eg `cargo bench -- day_15` or `cargo bench -- part_b`
+fn days(c: &mut Criterion) {
+    // unfinished parts panic, they are skipped instead of aborting the whole run
+    panic::set_hook(Box::new(|_| {}));
+
+    for puzzle in PUZZLES {
+        // checked before the group is opened, so every group that's opened gets finished
+        let input = match puzzle.parse(puzzle.input()) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+
+        let mut group = c.benchmark_group(format!("day_{:02}", puzzle.day()));
+        group.bench_function("parse", |b| {
+            b.iter(|| puzzle.parse(black_box(puzzle.input())))
+        });
+
+        for part in [Part::A, Part::B] {
+            let start = Instant::now();
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                puzzle.solve(part, input.as_ref());
+            }));
+            if solved.is_err() {
+                continue;
+            }
+
+            // keep slow parts from taking minutes, set for every part as the group keeps it
+            let slow = start.elapsed() > Duration::from_millis(100);
+            group.sample_size(if slow { 10 } else { DEFAULT_SAMPLE_SIZE });
+
+            group.bench_function(format!("part_{}", part), |b| {
+                b.iter(|| puzzle.solve(part, black_box(input.as_ref())))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -1,21 +1,29 @@
-pub fn part_a() -> u64 {
-    include_str!("../input.txt")
-        .lines()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<_>>()
-        .as_slice()
-        .windows(2)
-        .filter(|s| s[1] > s[0])
-        .count() as u64
-}
+use utils::{parse_token, Answer, ParseError, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Sonar Sweep";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-pub fn part_b() -> u64 {
-    include_str!("../input.txt")
-        .lines()
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<_>>()
-        .as_slice()
-        .windows(4)
-        .filter(|&s| s[0..3].iter().sum::<i64>() < s[1..4].iter().sum())
-        .count() as u64
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|x| parse_token(input, x)).collect()
+    }
+
+    fn part_a(input: &Self::Input) -> Answer {
+        (input.windows(2).filter(|s| s[1] > s[0]).count() as u64).into()
+    }
+
+    fn part_b(input: &Self::Input) -> Answer {
+        let count = input
+            .windows(4)
+            .filter(|&s| s[0..3].iter().sum::<i64>() < s[1..4].iter().sum())
+            .count();
+        (count as u64).into()
+    }
 }
+
+utils::example_tests!(Day01);
@@ -1,35 +1,60 @@
-pub fn part_a() -> u64 {
-    let (position, depth) = include_str!("../input.txt")
-        .lines()
-        .map(|line| line.split(" ").collect::<Vec<_>>())
-        .map(|x| (x[0], x[1].parse::<i64>().unwrap()))
-        .fold(
+use utils::{parse_token, Answer, ParseError, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<(String, i64)>;
+
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Dive!";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (direction, level) = line
+                    .split_once(' ')
+                    .ok_or_else(|| ParseError::at(input, line, "expected `<direction> <level>`"))?;
+                if !["forward", "down", "up"].contains(&direction) {
+                    let message = format!("unknown direction `{}`", direction);
+                    return Err(ParseError::at(input, direction, message));
+                }
+                Ok((direction.to_string(), parse_token(input, level)?))
+            })
+            .collect()
+    }
+
+    fn part_a(input: &Self::Input) -> Answer {
+        let (position, depth) = input.iter().fold(
             (0, 0),
-            |(position, depth), (direction, level)| match direction {
+            |(position, depth), (direction, level)| match direction.as_str() {
                 "forward" => (position + level, depth),
                 "down" => (position, depth + level),
                 "up" => (position, depth - level),
-                _ => unimplemented!(),
+                _ => unreachable!(),
             },
         );
 
-    (position * depth) as u64
-}
+        ((position * depth) as u64).into()
+    }
 
-pub fn part_b() -> u64 {
-    let (position, depth, _) = include_str!("../input.txt")
-        .lines()
-        .map(|line| line.split(" ").collect::<Vec<_>>())
-        .map(|x| (x[0], x[1].parse::<i64>().unwrap()))
-        .fold(
-            (0, 0, 0),
-            |(position, depth, aim), (direction, level)| match direction {
-                "forward" => (position + level, depth + (aim * level), aim),
-                "down" => (position, depth, aim + level),
-                "up" => (position, depth, aim - level),
-                _ => unimplemented!(),
-            },
-        );
+    fn part_b(input: &Self::Input) -> Answer {
+        let (position, depth, _) =
+            input
+                .iter()
+                .fold(
+                    (0, 0, 0),
+                    |(position, depth, aim), (direction, level)| match direction.as_str() {
+                        "forward" => (position + level, depth + (aim * level), aim),
+                        "down" => (position, depth, aim + level),
+                        "up" => (position, depth, aim - level),
+                        _ => unreachable!(),
+                    },
+                );
 
-    (position * depth) as u64
+        ((position * depth) as u64).into()
+    }
 }
+
+utils::example_tests!(Day02);
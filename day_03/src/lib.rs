@@ -1,28 +1,49 @@
-pub const BITS: usize = 12;
+use utils::{debug, Answer, ParseError, Solution};
 
-pub fn read_numbers(input: Option<&str>) -> Vec<usize> {
-    input
-        .unwrap_or(include_str!("../input.txt"))
+// the puzzle input has 12 bits per number, the example only 5
+#[derive(Debug)]
+pub struct Report {
+    width: usize,
+    numbers: Vec<usize>,
+}
+
+pub fn read_numbers(input: &str) -> Result<Report, ParseError> {
+    let width = match input.lines().next() {
+        None => return Err(ParseError::eof(input, "expected a binary number")),
+        Some("") => return Err(ParseError::at(input, input, "expected a binary number")),
+        Some(first) => first.len(),
+    };
+    let numbers = input
         .lines()
-        .map(|line| usize::from_str_radix(line, 2).unwrap())
-        .collect::<Vec<_>>()
+        .map(|line| {
+            if line.len() != width {
+                let message = format!("expected {} bits, found {}", width, line.len());
+                return Err(ParseError::at(input, line, message));
+            }
+            if let Some(i) = line.find(|c| c != '0' && c != '1') {
+                return Err(ParseError::at(input, &line[i..], "expected `0` or `1`"));
+            }
+            usize::from_str_radix(line, 2).map_err(|e| ParseError::at(input, line, e.to_string()))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Report { width, numbers })
 }
 
-pub fn find_most_common(numbers: &Vec<usize>) -> usize {
-    bits_to_num(&find_most_common_bits(numbers))
+pub fn find_most_common(numbers: &[usize], width: usize) -> usize {
+    bits_to_num(&find_most_common_bits(numbers, width))
 }
 
-fn count_ones(numbers: &Vec<usize>) -> [usize; BITS] {
-    numbers.iter().fold([0; BITS], |mut counts, &x| {
-        for i in 0..BITS {
-            counts[BITS - i - 1] += get_bit_at_position(x, i)
+fn count_ones(numbers: &[usize], width: usize) -> Vec<usize> {
+    numbers.iter().fold(vec![0; width], |mut counts, &x| {
+        for i in 0..width {
+            counts[width - i - 1] += get_bit_at_position(x, i)
         }
         counts
     })
 }
 
-fn find_most_common_bits(numbers: &Vec<usize>) -> Vec<usize> {
-    let ones = count_ones(numbers);
+fn find_most_common_bits(numbers: &[usize], width: usize) -> Vec<usize> {
+    let ones = count_ones(numbers, width);
     let n = numbers.len();
 
     ones.iter()
@@ -38,61 +59,79 @@ fn get_bit_at_position(num: usize, pos: usize) -> usize {
     (num & (1 << pos)) >> pos
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let numbers = read_numbers(input);
-    let gamma = find_most_common(&numbers);
-    // invert lower 12 bits
-    let epsilon = gamma ^ ((1 << 12) - 1);
+pub struct Day03;
 
-    println!("gamma: {}, eps: {}", gamma, epsilon);
-    println!("{}", gamma * epsilon);
+impl Solution for Day03 {
+    type Input = Report;
 
-    (gamma * epsilon) as u64
-}
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Binary Diagnostic";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let numbers = read_numbers(input);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_numbers(input)
+    }
 
-    let mut oxygen = numbers.clone();
-    for i in (0..BITS).rev() {
-        let mode = find_most_common(&oxygen);
+    fn part_a(&Report { width, ref numbers }: &Self::Input) -> Answer {
+        let gamma = find_most_common(numbers, width);
+        // invert lower `width` bits
+        let epsilon = gamma ^ ((1 << width) - 1);
 
-        oxygen = oxygen
-            .into_iter()
-            .filter(|&x| (x & (1 << i)) == (mode & (1 << i)))
-            .collect();
+        debug!("gamma: {}, eps: {}", gamma, epsilon);
+        debug!("{}", gamma * epsilon);
 
-        if oxygen.len() == 1 {
-            break;
-        }
+        ((gamma * epsilon) as u64).into()
     }
 
-    let mut co2 = numbers;
-    for i in (0..BITS).rev() {
-        let mode = find_most_common(&co2);
+    fn part_b(&Report { width, ref numbers }: &Self::Input) -> Answer {
+        let mut oxygen = numbers.clone();
+        for i in (0..width).rev() {
+            let mode = find_most_common(&oxygen, width);
 
-        co2 = co2
-            .into_iter()
-            .filter(|&x| (x & (1 << i)) != (mode & (1 << i)))
-            .collect();
+            oxygen.retain(|&x| (x & (1 << i)) == (mode & (1 << i)));
 
-        if co2.len() == 1 {
-            break;
+            if oxygen.len() == 1 {
+                break;
+            }
         }
-    }
 
-    (oxygen[0] * co2[0]) as u64
+        let mut co2 = numbers.clone();
+        for i in (0..width).rev() {
+            let mode = find_most_common(&co2, width);
+
+            co2.retain(|&x| (x & (1 << i)) != (mode & (1 << i)));
+
+            if co2.len() == 1 {
+                break;
+            }
+        }
+
+        ((oxygen[0] * co2[0]) as u64).into()
+    }
 }
 
+utils::example_tests!(Day03);
+
 #[cfg(test)]
 mod tests {
+    use super::Day03;
+    use utils::{solve, Part};
+
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 775304);
+        assert_eq!(solve::<Day03>(Part::A, None).unwrap(), 775304);
     }
 
     #[test]
     fn test_part_b() {
-        assert_eq!(super::part_b(None), 1370737);
+        assert_eq!(solve::<Day03>(Part::B, None).unwrap(), 1370737);
+    }
+
+    #[test]
+    fn test_read_numbers_rejects_bad_widths() {
+        assert!(super::read_numbers("").is_err());
+        assert!(super::read_numbers("\n0101").is_err());
+        let error = super::read_numbers(&"1".repeat(65)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
     }
 }
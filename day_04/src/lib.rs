@@ -1,8 +1,9 @@
 use std::cell::RefCell;
+use utils::{parse_token, Answer, ParseError, Solution};
 
 const N: usize = 5;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Board {
     pub nums: Vec<Vec<i64>>,
 }
@@ -19,21 +20,13 @@ impl Board {
     }
 
     pub fn is_winner(&self) -> bool {
-        if self
-            .nums
+        self.nums
             .iter()
             .map(|row| row.iter().sum::<i64>())
             .any(|x| x == -(N as i64))
-        {
-            true
-        } else if (0..N)
-            .map(|j| self.nums.iter().map(|row| row[j]).sum::<i64>())
-            .any(|x| x == -(N as i64))
-        {
-            true
-        } else {
-            false
-        }
+            || (0..N)
+                .map(|j| self.nums.iter().map(|row| row[j]).sum::<i64>())
+                .any(|x| x == -(N as i64))
     }
     pub fn get_score(&self) -> i64 {
         self.nums
@@ -43,86 +36,126 @@ impl Board {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Bingo {
     pub order: Vec<i64>,
     pub boards: Vec<RefCell<Board>>,
 }
 
-pub fn read_input(input: Option<&str>) -> Bingo {
-    let mut lines = input.unwrap_or(include_str!("../input.txt")).lines();
+pub fn read_input(input: &str) -> Result<Bingo, ParseError> {
+    let mut lines = input.lines();
     let order = lines
         .next()
-        .unwrap()
+        .ok_or_else(|| ParseError::eof(input, "expected the drawn numbers"))?
         .split(",")
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
+        .map(|x| parse_token(input, x))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let lines = lines.filter(|line| line.len() > 0).collect::<Vec<_>>();
+    let lines = lines.filter(|line| !line.is_empty()).collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        return Err(ParseError::eof(input, "expected at least one board"));
+    }
 
     let boards = lines
         .chunks(N)
         .map(|lines| {
+            if lines.len() != N {
+                let message = format!("expected a board of {} rows", N);
+                return Err(ParseError::at(input, lines[0], message));
+            }
+
             lines
                 .iter()
                 .map(|l| {
-                    l.split_whitespace()
-                        .map(|x| x.parse::<i64>().unwrap())
-                        .collect::<Vec<_>>()
+                    let row = l
+                        .split_whitespace()
+                        .map(|x| parse_token(input, x))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if row.len() != N {
+                        let message = format!("expected {} numbers in a row", N);
+                        return Err(ParseError::at(input, l, message));
+                    }
+                    Ok(row)
                 })
-                .collect::<Vec<_>>()
+                .collect::<Result<Vec<_>, _>>()
         })
-        .map(|b| RefCell::new(Board { nums: b }))
-        .collect();
+        .map(|b| Ok(RefCell::new(Board { nums: b? })))
+        .collect::<Result<_, _>>()?;
 
-    Bingo { order, boards }
+    Ok(Bingo { order, boards })
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let mut bingo = read_input(input);
-    let mut last_winner_score: i64 = -1;
+pub struct Day04;
 
-    for value in bingo.order.into_iter() {
-        bingo.boards.retain(|board| {
-            board.borrow_mut().set(value);
-            let winner = board.borrow().is_winner();
-            if winner {
-                last_winner_score = board.borrow().get_score();
-            }
-            !winner
-        });
+impl Solution for Day04 {
+    type Input = Bingo;
+
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Giant Squid";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-        if bingo.boards.len() == 0 {
-            return (last_winner_score * value) as u64;
+    fn part_a(input: &Self::Input) -> Answer {
+        let bingo = input.clone();
+
+        // find first winner
+        for value in bingo.order.into_iter() {
+            for board in &bingo.boards {
+                board.borrow_mut().set(value);
+                if board.borrow().is_winner() {
+                    return ((board.borrow().get_score() * value) as u64).into();
+                }
+            }
         }
+        unreachable!()
     }
-    unreachable!()
-}
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let bingo = read_input(input);
+    fn part_b(input: &Self::Input) -> Answer {
+        let mut bingo = input.clone();
+        let mut last_winner_score: i64 = -1;
+
+        for value in bingo.order.into_iter() {
+            bingo.boards.retain(|board| {
+                board.borrow_mut().set(value);
+                let winner = board.borrow().is_winner();
+                if winner {
+                    last_winner_score = board.borrow().get_score();
+                }
+                !winner
+            });
 
-    // find first winner
-    for value in bingo.order.into_iter() {
-        for board in &bingo.boards {
-            board.borrow_mut().set(value);
-            if board.borrow().is_winner() {
-                return (board.borrow().get_score() * value) as u64;
+            if bingo.boards.is_empty() {
+                return ((last_winner_score * value) as u64).into();
             }
         }
+        unreachable!()
     }
-    unreachable!()
 }
 
+utils::example_tests!(Day04);
+
 #[cfg(test)]
 mod tests {
+    use super::Day04;
+    use utils::{solve, Part};
+
     #[test]
     fn test_part_a_works() {
-        assert_eq!(super::part_a(None), 123);
+        assert_eq!(solve::<Day04>(Part::A, None).unwrap(), 21607);
     }
 
     #[test]
     fn test_part_b_works() {
-        assert_eq!(super::part_b(None), 123);
+        assert_eq!(solve::<Day04>(Part::B, None).unwrap(), 19012);
+    }
+
+    #[test]
+    fn test_no_boards() {
+        let error = super::read_input("7,4,9\n\n").unwrap_err();
+        assert_eq!(error.message, "expected at least one board");
     }
 }
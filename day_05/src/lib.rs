@@ -1,3 +1,5 @@
+use utils::{parse_token, Answer, ParseError, Solution};
+
 type Point = (usize, usize);
 type Line = (Point, Point);
 
@@ -6,32 +8,35 @@ enum Direction {
     Down,
 }
 
-fn read_input(input: Option<&str>) -> impl Iterator<Item = Line> + '_ {
-    input
-        .unwrap_or(include_str!("../input.txt"))
-        .lines()
-        .map(|line| {
-            let mut s = line.split(" -> ").map(|pair| {
-                let mut s = pair.split(",").map(|x| x.parse::<usize>().unwrap());
-                (s.next().unwrap(), s.next().unwrap())
-            });
-
-            (s.next().unwrap(), s.next().unwrap())
-        })
+fn read_point(input: &str, pair: &str) -> Result<Point, ParseError> {
+    let (x, y) = pair
+        .split_once(",")
+        .ok_or_else(|| ParseError::at(input, pair, "expected `x,y`"))?;
+    Ok((parse_token(input, x)?, parse_token(input, y)?))
+}
+
+fn read_input(input: &str) -> impl Iterator<Item = Result<Line, ParseError>> + '_ {
+    input.lines().map(|line| {
+        let (a, b) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at(input, line, "expected `x1,y1 -> x2,y2`"))?;
+        Ok((read_point(input, a)?, read_point(input, b)?))
+    })
 }
 
+// part a leaves out the diagonal lines, so there may be none left
 fn calculate_shape(input: &[Line]) -> (usize, usize) {
-    let n = 1 + *input
+    let n = 1 + input
         .iter()
-        .map(|((x1, _), (x2, _))| x1.max(x2))
+        .map(|((x1, _), (x2, _))| *x1.max(x2))
         .max()
-        .unwrap();
+        .unwrap_or(0);
 
-    let m = 1 + *input
+    let m = 1 + input
         .iter()
-        .map(|((_, y1), (_, y2))| y1.max(y2))
+        .map(|((_, y1), (_, y2))| *y1.max(y2))
         .max()
-        .unwrap();
+        .unwrap_or(0);
 
     (n, m)
 }
@@ -40,8 +45,10 @@ fn abs_diff(a: &usize, b: &usize) -> usize {
     a.max(b) - a.min(b)
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let input = read_input(input)
+fn count_overlaps_a(input: &[Line]) -> u64 {
+    let input = input
+        .iter()
+        .copied()
         .filter(|((x1, y1), (x2, y2))| x1 == x2 || y1 == y2)
         .collect::<Vec<_>>();
 
@@ -51,12 +58,12 @@ pub fn part_a(input: Option<&str>) -> u64 {
 
     for ((x1, y1), (x2, y2)) in input {
         if x1 == x2 {
-            for y in y1.min(y2)..=y1.max(y2) {
-                grid[x1][y] += 1;
+            for cell in &mut grid[x1][y1.min(y2)..=y1.max(y2)] {
+                *cell += 1;
             }
         } else if y1 == y2 {
-            for x in x1.min(x2)..=x1.max(x2) {
-                grid[x][y1] += 1;
+            for row in &mut grid[x1.min(x2)..=x1.max(x2)] {
+                row[y1] += 1;
             }
         }
     }
@@ -64,8 +71,10 @@ pub fn part_a(input: Option<&str>) -> u64 {
     grid.into_iter().flatten().filter(|&x| x > 1).count() as u64
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let input = read_input(input)
+fn count_overlaps_b(input: &[Line]) -> u64 {
+    let input = input
+        .iter()
+        .copied()
         .filter(|((x1, y1), (x2, y2))| x1 == x2 || y1 == y2 || abs_diff(x1, x2) == abs_diff(y1, y2))
         .collect::<Vec<_>>();
 
@@ -75,12 +84,12 @@ pub fn part_b(input: Option<&str>) -> u64 {
 
     for ((x1, y1), (x2, y2)) in input {
         if x1 == x2 {
-            for y in y1.min(y2)..=y1.max(y2) {
-                grid[x1][y] += 1;
+            for cell in &mut grid[x1][y1.min(y2)..=y1.max(y2)] {
+                *cell += 1;
             }
         } else if y1 == y2 {
-            for x in x1.min(x2)..=x1.max(x2) {
-                grid[x][y1] += 1;
+            for row in &mut grid[x1.min(x2)..=x1.max(x2)] {
+                row[y1] += 1;
             }
         } else if abs_diff(&x1, &x2) == abs_diff(&y1, &y2) {
             let length = abs_diff(&x1, &x2);
@@ -115,3 +124,46 @@ pub fn part_b(input: Option<&str>) -> u64 {
 
     grid.into_iter().flatten().filter(|&x| x > 1).count() as u64
 }
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Line>;
+
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Hydrothermal Venture";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = read_input(input).collect::<Result<Vec<_>, _>>()?;
+        if lines.is_empty() {
+            return Err(ParseError::eof(input, "expected at least one line"));
+        }
+        Ok(lines)
+    }
+
+    fn part_a(input: &Self::Input) -> Answer {
+        count_overlaps_a(input).into()
+    }
+
+    fn part_b(input: &Self::Input) -> Answer {
+        count_overlaps_b(input).into()
+    }
+}
+
+utils::example_tests!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::Day05;
+    use utils::{solve, Part, Solution};
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            Day05::parse("").unwrap_err().message,
+            "expected at least one line"
+        );
+        assert_eq!(solve::<Day05>(Part::A, Some("0,0 -> 2,2")).unwrap(), 0);
+    }
+}
@@ -1,16 +1,18 @@
 use std::collections::HashMap;
+use utils::{parse_token, Answer, ParseError, Solution};
 
-fn read_input(input: Option<&str>) -> HashMap<u8, u64> {
+fn read_input(input: &str) -> Result<HashMap<u8, u64>, ParseError> {
     let mut result = HashMap::with_capacity(8);
 
-    input
-        .unwrap_or(include_str!("../input.txt"))
-        .trim_end()
-        .split(",")
-        .map(|x| x.parse::<u8>().unwrap())
-        .for_each(|x| *result.entry(x).or_insert(0) += 1);
+    for x in input.trim_end().split(",") {
+        let timer = parse_token::<u8>(input, x)?;
+        if timer > 8 {
+            return Err(ParseError::at(input, x, "timer has to be between 0 and 8"));
+        }
+        *result.entry(timer).or_insert(0) += 1;
+    }
 
-    result
+    Ok(result)
 }
 
 fn simulate(mut state: HashMap<u8, u64>, n: usize) -> u64 {
@@ -38,35 +40,42 @@ fn simulate(mut state: HashMap<u8, u64>, n: usize) -> u64 {
     state.into_values().sum()
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let initial_state = read_input(input);
-    simulate(initial_state, 80)
-}
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = HashMap<u8, u64>;
+
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Lanternfish";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(initial_state: &Self::Input) -> Answer {
+        simulate(initial_state.clone(), 80).into()
+    }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let initial_state = read_input(input);
-    simulate(initial_state, 256)
+    fn part_b(initial_state: &Self::Input) -> Answer {
+        simulate(initial_state.clone(), 256).into()
+    }
 }
 
+utils::example_tests!(Day06);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        assert_eq!(super::part_a(Some("3,4,3,1,2")), 5934);
-    }
+    use super::Day06;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 362639);
-    }
-
-    #[test]
-    fn test_part_b_works() {
-        assert_eq!(super::part_b(Some("3,4,3,1,2")), 26984457539);
+        assert_eq!(solve::<Day06>(Part::A, None).unwrap(), 362639);
     }
 
     #[test]
     fn test_part_b() {
-        assert_eq!(super::part_b(None), 1639854996917);
+        assert_eq!(solve::<Day06>(Part::B, None).unwrap(), 1639854996917);
     }
 }
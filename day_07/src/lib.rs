@@ -1,19 +1,20 @@
+use utils::{parse_token, Answer, ParseError, Solution};
+
 mod stats;
 
-fn read_input(path: Option<&str>) -> Vec<i32> {
-    std::fs::read_to_string(path.unwrap_or(&format!("{}/input.txt", env!("CARGO_MANIFEST_DIR"))))
-        .unwrap()
+fn read_input(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
         .trim_end()
         .split(",")
-        .map(|x| x.parse().unwrap())
+        .map(|x| parse_token(input, x))
         .collect()
 }
 
-fn calculate_fuel(crabs: &Vec<i32>, alignment_point: i32) -> i32 {
+fn calculate_fuel(crabs: &[i32], alignment_point: i32) -> i32 {
     crabs.iter().map(|&x| (x - alignment_point).abs()).sum()
 }
 
-fn calculate_fuel_2(crabs: &Vec<i32>, alignment_point: i32) -> i32 {
+fn calculate_fuel_2(crabs: &[i32], alignment_point: i32) -> i32 {
     crabs
         .iter()
         .map(|&x| {
@@ -23,56 +24,59 @@ fn calculate_fuel_2(crabs: &Vec<i32>, alignment_point: i32) -> i32 {
         .sum()
 }
 
-pub fn part_a(path: Option<&str>) -> u64 {
-    let input = read_input(path);
-    let start = stats::median(&input).unwrap().round() as i32;
-    calculate_fuel(&input, start) as u64
-}
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<i32>;
+
+    const DAY: u8 = 7;
+    const NAME: &'static str = "The Treachery of Whales";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(input: &Self::Input) -> Answer {
+        let start = stats::median(input).unwrap().round() as i32;
+        (calculate_fuel(input, start) as u64).into()
+    }
 
-pub fn part_b(path: Option<&str>) -> u64 {
-    let input = read_input(path);
-    let start = stats::median(&input).unwrap().round() as i32;
+    fn part_b(input: &Self::Input) -> Answer {
+        let start = stats::median(input).unwrap().round() as i32;
 
-    let mut best_score = calculate_fuel_2(&input, start);
+        let mut best_score = calculate_fuel_2(input, start);
 
-    for direction in [-1, 1] {
-        for i in 1..input.len() {
-            let point = start + (i as i32) * direction;
-            let score = calculate_fuel_2(&input, point);
-            if score > best_score {
-                break;
-            } else {
-                best_score = score;
+        for direction in [-1, 1] {
+            for i in 1..input.len() {
+                let point = start + (i as i32) * direction;
+                let score = calculate_fuel_2(input, point);
+                if score > best_score {
+                    break;
+                } else {
+                    best_score = score;
+                }
             }
         }
-    }
 
-    best_score as u64
+        (best_score as u64).into()
+    }
 }
 
+utils::example_tests!(Day07);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_1_works() {
-        let path = format!("{}/example.txt", env!("CARGO_MANIFEST_DIR"));
-        assert_eq!(super::part_a(Some(&path)), 37);
-    }
+    use super::Day07;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_1() {
-        let path = format!("{}/input.txt", env!("CARGO_MANIFEST_DIR"));
-        assert_eq!(super::part_a(Some(&path)), 340052);
-    }
-
-    #[test]
-    fn test_part_2_works() {
-        let path = format!("{}/example.txt", env!("CARGO_MANIFEST_DIR"));
-        assert_eq!(super::part_b(Some(&path)), 168);
+        assert_eq!(solve::<Day07>(Part::A, None).unwrap(), 340052);
     }
 
     #[test]
     fn test_part_2() {
-        let path = format!("{}/input.txt", env!("CARGO_MANIFEST_DIR"));
-        assert_eq!(super::part_b(Some(&path)), 92948968);
+        assert_eq!(solve::<Day07>(Part::B, None).unwrap(), 92948968);
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use utils::{trace, Answer, ParseError, Solution};
 
 //        -gfedcba
 // a   => 00000001
@@ -15,12 +16,12 @@ impl std::fmt::Display for Signal {
 
 impl From<&str> for Signal {
     fn from(s: &str) -> Self {
-        let bitmask = s.chars().fold(0u8, |a, c| a | (1 << (c as u8 - 'a' as u8)));
+        let bitmask = s.chars().fold(0u8, |a, c| a | (1 << (c as u8 - b'a')));
         Self(bitmask)
     }
 }
 
-struct Entry {
+pub struct Entry {
     input: Vec<Signal>,
     output: Vec<Signal>,
 }
@@ -30,7 +31,7 @@ struct Decoder {
 }
 
 impl Decoder {
-    fn new(data: &Vec<Signal>) -> Self {
+    fn new(data: &[Signal]) -> Self {
         let mut codes: HashMap<Signal, u8> = HashMap::with_capacity(10);
         let mut index: [u8; 10] = [0; 10];
 
@@ -67,7 +68,7 @@ impl Decoder {
                     break;
                 }
             }
-            println!("{:?}", codes);
+            trace!("{:?}", codes);
         }
 
         Self { codes }
@@ -77,60 +78,92 @@ impl Decoder {
         *self.codes.get(signal).unwrap() as u16
     }
 
-    fn decode_line(&self, signals: &Vec<Signal>) -> u16 {
+    fn decode_line(&self, signals: &[Signal]) -> u16 {
         signals
             .iter()
             .rev()
             .enumerate()
             .map(|(i, x)| 10_u16.pow(i as u32) * self.decode(x))
-            .sum::<u16>() as u16
+            .sum::<u16>()
     }
 }
 
-fn parse_signals(signals: &str) -> Vec<Signal> {
-    signals
+fn parse_signals(input: &str, text: &str, n: usize) -> Result<Vec<Signal>, ParseError> {
+    if let Some(i) = text.find(|c: char| c != ' ' && !('a'..='g').contains(&c)) {
+        return Err(ParseError::at(
+            input,
+            &text[i..],
+            "expected a segment `a` to `g`",
+        ));
+    }
+
+    let signals = text
         .split_whitespace()
         .map(|x| x.into())
-        .collect::<Vec<_>>()
+        .collect::<Vec<_>>();
+    if signals.len() != n {
+        let message = format!("expected {} signals, found {}", n, signals.len());
+        return Err(ParseError::at(input, text, message));
+    }
+
+    Ok(signals)
 }
 
-fn parse_input<'a>(input: &'a str) -> Vec<Entry> {
+fn parse_input(input: &str) -> Result<Vec<Entry>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut s = line.split(" | ");
-            let input = parse_signals(s.next().unwrap());
-            let output = parse_signals(s.next().unwrap());
-            Entry { input, output }
+            let (patterns, output) = line
+                .split_once(" | ")
+                .ok_or_else(|| ParseError::at(input, line, "expected `<patterns> | <output>`"))?;
+            Ok(Entry {
+                input: parse_signals(input, patterns, 10)?,
+                output: parse_signals(input, output, 4)?,
+            })
         })
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let data = parse_input(input.unwrap_or(include_str!("../input.txt")));
+pub struct Day08;
 
-    data.into_iter()
-        .map(|e| {
-            e.output
-                .iter()
-                .filter(|x| [2, 3, 4, 7].contains(&x.0.count_ones()))
-                .count()
-        })
-        .sum::<usize>() as u64
-}
+impl Solution for Day08 {
+    type Input = Vec<Entry>;
+
+    const DAY: u8 = 8;
+    const NAME: &'static str = "Seven Segment Search";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let data = parse_input(input.unwrap_or(include_str!("../input.txt")));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(data: &Self::Input) -> Answer {
+        let count = data
+            .iter()
+            .map(|e| {
+                e.output
+                    .iter()
+                    .filter(|x| [2, 3, 4, 7].contains(&x.0.count_ones()))
+                    .count()
+            })
+            .sum::<usize>();
+        (count as u64).into()
+    }
 
-    data.into_iter()
-        .map(|e| Decoder::new(&e.input).decode_line(&e.output) as u64)
-        .sum::<u64>()
+    fn part_b(data: &Self::Input) -> Answer {
+        data.iter()
+            .map(|e| Decoder::new(&e.input).decode_line(&e.output) as u64)
+            .sum::<u64>()
+            .into()
+    }
 }
 
+utils::example_tests!(Day08);
+
 #[cfg(test)]
 mod tests {
-
-    use super::Signal;
+    use super::{Day08, Signal};
+    use utils::{solve, Part};
 
     #[test]
     fn test_parse_signal_from_str() {
@@ -142,7 +175,8 @@ mod tests {
     fn test_decode() {
         let input = super::parse_input(
             "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
-        );
+        )
+        .unwrap();
         let line = input.first().unwrap();
         let decoder = super::Decoder::new(&line.input);
 
@@ -160,27 +194,15 @@ mod tests {
         assert_eq!(decoder.decode_line(&line.output), 5353);
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 26);
-    }
-
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day08>(Part::A, None).unwrap();
         assert_eq!(result, 303);
     }
 
-    #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 61229);
-    }
-
     #[test]
     fn test_part_b() {
-        let result = super::part_b(None);
+        let result = solve::<Day08>(Part::B, None).unwrap();
         assert_eq!(result, 961734);
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::ops::ControlFlow;
+use utils::{Answer, ParseError, Solution};
 
 fn match_bracket(
     mut stack: VecDeque<char>,
@@ -28,9 +29,8 @@ fn fold_line(line: &str) -> ControlFlow<u64, VecDeque<char>> {
     })
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
+fn syntax_error_score(input: &str) -> u64 {
     input
-        .unwrap_or(include_str!("../input.txt"))
         .lines()
         .map(|line| match fold_line(line) {
             ControlFlow::Break(score) => score,
@@ -39,9 +39,8 @@ pub fn part_a(input: Option<&str>) -> u64 {
         .sum()
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
+fn completion_score(input: &str) -> u64 {
     let mut scores = input
-        .unwrap_or(include_str!("../input.txt"))
         .lines()
         .map(|line| match fold_line(line) {
             ControlFlow::Break(_) => 0,
@@ -63,29 +62,47 @@ pub fn part_b(input: Option<&str>) -> u64 {
     scores[scores.len() / 2]
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Syntax Scoring";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        match input.find(|c: char| !"()[]{}<>\n".contains(c)) {
+            Some(i) => Err(ParseError::at(input, &input[i..], "expected a bracket")),
+            None => Ok(input.to_string()),
+        }
+    }
+
+    fn part_a(input: &Self::Input) -> Answer {
+        syntax_error_score(input).into()
+    }
+
+    fn part_b(input: &Self::Input) -> Answer {
+        completion_score(input).into()
+    }
+}
+
+utils::example_tests!(Day10);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 26397);
-    }
+    use super::Day10;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day10>(Part::A, None).unwrap();
         assert_eq!(result, 339411);
     }
 
-    #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 288957);
-    }
-
     #[test]
     fn test_part_b() {
-        let result = super::part_b(None);
+        let result = solve::<Day10>(Part::B, None).unwrap();
         assert_eq!(result, 2289754624);
     }
 }
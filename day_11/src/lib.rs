@@ -1,96 +1,89 @@
-use std::collections::HashSet;
-use utils::{Grid, Neighbors};
+use utils::automaton::Automaton;
+use utils::{Answer, Grid, Neighbors, ParseError, Solution};
 
-fn simulate(grid: &mut Grid<u8>, n: u32) -> u64 {
-    (0..n).fold(0, |acc, _i| acc + step(grid))
-}
+// energy levels only go up to 9 and a bit before flashing, so there's room for a
+// marker of the octopuses that already flashed during the step
+const FLASHED: u8 = u8::MAX;
 
-fn step(grid: &mut Grid<u8>) -> u64 {
-    let mut flushed: HashSet<(usize, usize)> = HashSet::new();
-    // the energy level of each octopus increases by 1
-    for i in 0..grid.shape.0 {
-        for j in 0..grid.shape.1 {
-            inc(grid, i, j);
-            try_flush(grid, i, j, &mut flushed);
-        }
-    }
-    reset(grid);
-    flushed.len() as u64
+fn octopuses(grid: Grid<u8>) -> Automaton<u8> {
+    Automaton::new(grid, Neighbors::Diagonal)
+        // the energy level of each octopus increases by 1
+        .phase(|&energy, _| energy + 1)
+        // the ones above 9 flash, which raises the level of the ones around them
+        .settle(|&energy, around| match energy {
+            10.. => FLASHED,
+            _ => energy + around.count(|&e| e > 9 && e != FLASHED) as u8,
+        })
+        .phase(|&energy, _| if energy == FLASHED { 0 } else { energy })
 }
 
-fn try_flush(grid: &mut Grid<u8>, i: usize, j: usize, flushed: &mut HashSet<(usize, usize)>) {
-    if !flushed.contains(&(i, j)) && grid.get(i, j) > &9 {
-        flushed.insert((i, j));
-        for (ni, nj) in grid.get_neighbors(i, j, Neighbors::Diagonal) {
-            inc(grid, ni, nj);
-            try_flush(grid, ni, nj, flushed)
-        }
+// the number of flashes
+fn simulate(grid: &mut Grid<u8>, n: u32) -> u64 {
+    let mut automaton = octopuses(grid.clone());
+    let mut flashes = 0;
+    for _ in 0..n {
+        automaton.step();
+        flashes += automaton.grid().iter().filter(|&&x| x == 0).count() as u64;
     }
+    *grid = automaton.into_grid();
+    flashes
 }
 
-fn reset(grid: &mut Grid<u8>) {
-    for i in 0..grid.shape.0 {
-        for j in 0..grid.shape.1 {
-            if grid.get(i, j) > &9 {
-                grid.set(i, j, 0);
-            }
-        }
-    }
+fn read_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    input.parse::<Grid<u8>>()
 }
 
-fn inc(grid: &mut Grid<u8>, i: usize, j: usize) {
-    grid.set(i, j, grid.get(i, j) + 1)
-}
+pub struct Day11;
 
-fn read_input(input: &str) -> Grid<u8> {
-    Grid::<u8>::from_str(input)
-}
+impl Solution for Day11 {
+    type Input = Grid<u8>;
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let mut grid = read_input(input.unwrap_or(include_str!("../input.txt")));
-    simulate(&mut grid, 100)
-}
+    const DAY: u8 = 11;
+    const NAME: &'static str = "Dumbo Octopus";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let mut grid = read_input(input.unwrap_or(include_str!("../input.txt")));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-    for i in 1..1000 {
-        step(&mut grid);
-        if grid.data.iter().flatten().all(|x| x == &0) {
-            return i as u64;
+    fn part_a(grid: &Self::Input) -> Answer {
+        simulate(&mut grid.clone(), 100).into()
+    }
+
+    fn part_b(grid: &Self::Input) -> Answer {
+        let mut automaton = octopuses(grid.clone());
+
+        for i in 1..1000 {
+            automaton.step();
+            if automaton.grid().iter().all(|x| x == &0) {
+                return (i as u64).into();
+            }
         }
+        unreachable!()
     }
-    unreachable!()
 }
 
+utils::example_tests!(Day11);
+
 #[cfg(test)]
 mod tests {
+    use super::Day11;
+    use utils::{solve, Part};
+
     #[test]
     fn test_flushes() {
         let input = "11111\n19991\n19191\n19991\n11111";
         let expected = "34543\n40004\n50005\n40004\n34543";
 
-        let mut grid = super::read_input(input);
+        let mut grid = super::read_input(input).unwrap();
         let flushes = super::simulate(&mut grid, 1);
-        assert_eq!(grid.data, super::read_input(expected).data);
+        assert_eq!(grid, super::read_input(expected).unwrap());
         assert_eq!(flushes, 9);
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let flushes = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(flushes, 1656);
-    }
-
-    #[test]
-    fn test_part_b_works() {
-        let step = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(step, 195);
-    }
-
     #[test]
     fn test_part_b() {
-        let step = super::part_b(None);
+        let step = solve::<Day11>(Part::B, None).unwrap();
         assert_eq!(step, 220);
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use utils::{Answer, Command, ParseError, Solution};
 
 struct Counter(u64);
 
@@ -11,29 +12,34 @@ impl Counter {
     }
 }
 
-struct Graph<'a> {
-    adj_list: HashMap<&'a str, Vec<&'a str>>,
+pub struct Graph {
+    adj_list: HashMap<String, Vec<String>>,
 }
 
-impl<'a> Graph<'a> {
+impl Graph {
     fn new() -> Self {
         Self {
             adj_list: HashMap::new(),
         }
     }
 
-    fn add_edge(mut self, src: &'a str, dest: &'a str) -> Graph<'a> {
+    fn add_edge(mut self, src: &str, dest: &str) -> Graph {
         for (a, b) in [(src, dest), (dest, src)] {
-            self.adj_list.entry(a).or_insert(Vec::new()).push(b);
+            self.adj_list
+                .entry(a.to_string())
+                .or_default()
+                .push(b.to_string());
         }
         self
     }
 }
 
-fn read_input<'a>(input: &'a str) -> Graph<'a> {
-    input.lines().fold(Graph::new(), |graph, line| {
-        let (a, b) = line.split_once("-").unwrap();
-        graph.add_edge(a, b)
+fn read_input(input: &str) -> Result<Graph, ParseError> {
+    input.lines().try_fold(Graph::new(), |graph, line| {
+        let (a, b) = line
+            .split_once("-")
+            .ok_or_else(|| ParseError::at(input, line, "expected `<cave>-<cave>`"))?;
+        Ok(graph.add_edge(a, b))
     })
 }
 
@@ -52,7 +58,7 @@ fn count_a(
     if src == dest {
         counter.inc();
     } else {
-        for &adj in graph.adj_list.get(src).unwrap_or(&Vec::new()) {
+        for adj in graph.adj_list.get(src).unwrap_or(&Vec::new()) {
             if !visited.contains(adj) {
                 count_a(graph, adj, dest, visited, counter)
             }
@@ -73,9 +79,10 @@ fn count_b<'a>(
         counter.inc();
         // println!("{:?}", path);
     } else {
-        for &adj in graph.adj_list.get(src).unwrap_or(&Vec::new()) {
-            let a = &adj.to_lowercase() == adj && !v && path.contains(&adj) && adj != "start";
-            if &adj.to_uppercase() == adj || !path.contains(&adj) || a {
+        for adj in graph.adj_list.get(src).into_iter().flatten() {
+            let adj = adj.as_str();
+            let a = adj.to_lowercase() == adj && !v && path.contains(&adj) && adj != "start";
+            if adj.to_uppercase() == adj || !path.contains(&adj) || a {
                 let v = v || a;
                 path.push(adj);
                 count_b(graph, adj, dest, path, counter, v);
@@ -88,29 +95,82 @@ fn count_b<'a>(
 fn path_count_a(graph: &Graph, src: &str, dest: &str) -> u64 {
     let mut visited = HashSet::new();
     let mut counter = Counter::new();
-    count_a(&graph, src, dest, &mut visited, &mut counter);
+    count_a(graph, src, dest, &mut visited, &mut counter);
     counter.0
 }
 
 fn path_count_b(graph: &Graph, src: &str, dest: &str) -> u64 {
     let mut counter = Counter::new();
     let mut path = vec!["start"];
-    count_b(&graph, src, dest, &mut path, &mut counter, false);
+    count_b(graph, src, dest, &mut path, &mut counter, false);
     counter.0
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let graph = read_input(input.unwrap_or(include_str!("../input.txt")));
-    path_count_a(&graph, "start", "end")
+fn show(graph: &mut Graph, _args: &str) -> Result<String, String> {
+    let mut caves = graph.adj_list.iter().collect::<Vec<_>>();
+    caves.sort();
+    Ok(caves
+        .into_iter()
+        .map(|(cave, adj)| format!("{}: {}", cave, adj.join(", ")))
+        .collect::<Vec<_>>()
+        .join("\n"))
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let graph = read_input(input.unwrap_or(include_str!("../input.txt")));
-    path_count_b(&graph, "start", "end")
+fn paths(graph: &mut Graph, args: &str) -> Result<String, String> {
+    let (src, dest) = match args.split_whitespace().collect::<Vec<_>>()[..] {
+        [] => ("start", "end"),
+        [src, dest] => (src, dest),
+        _ => return Err("expected `paths [<from> <to>]`".to_string()),
+    };
+    for cave in [src, dest] {
+        if !graph.adj_list.contains_key(cave) {
+            return Err(format!("no cave `{}`", cave));
+        }
+    }
+    Ok(path_count_a(graph, src, dest).to_string())
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph;
+
+    const DAY: u8 = 12;
+    const NAME: &'static str = "Passage Pathing";
+    const INPUT: &'static str = include_str!("../input.txt");
+    const COMMANDS: &'static [Command<Self::Input>] = &[
+        Command {
+            name: "show",
+            help: "list every cave with its neighbours",
+            run: show,
+        },
+        Command {
+            name: "paths",
+            help: "count the paths between two caves, `start` and `end` by default",
+            run: paths,
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(graph: &Self::Input) -> Answer {
+        path_count_a(graph, "start", "end").into()
+    }
+
+    fn part_b(graph: &Self::Input) -> Answer {
+        path_count_b(graph, "start", "end").into()
+    }
 }
 
+utils::example_tests!(Day12);
+
 #[cfg(test)]
 mod tests {
+    use super::Day12;
+    use utils::{solve, Part};
+
     #[test]
     fn test_example_works() {
         let graph = super::Graph::new()
@@ -126,27 +186,15 @@ mod tests {
         assert_eq!(super::path_count_b(&graph, "start", "end"), 36);
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 226);
-    }
-
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day12>(Part::A, None).unwrap();
         assert_eq!(result, 4775);
     }
 
-    #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 3509);
-    }
-
     #[test]
     fn test_part_b() {
-        let result = super::part_b(None);
+        let result = solve::<Day12>(Part::B, None).unwrap();
         assert_eq!(result, 152480);
     }
 }
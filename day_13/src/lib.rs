@@ -1,36 +1,43 @@
-use std::collections::HashSet;
+use utils::{decode_letters, parse_token, Answer, ParseError, Solution, SparseGrid};
 
 type Point = (u16, u16);
 
-enum Fold {
+pub enum Fold {
     X(u16),
     Y(u16),
 }
 
-fn read_input(input: &str) -> (Vec<Point>, Vec<Fold>) {
+fn read_input(input: &str) -> Result<(Vec<Point>, Vec<Fold>), ParseError> {
     let points = input
         .lines()
-        .map_while(|line| {
-            if line.is_empty() {
-                None
-            } else {
-                let (x, y) = line.split_once(",").unwrap();
-                Some((x.parse().unwrap(), y.parse().unwrap()))
-            }
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (x, y) = line
+                .split_once(",")
+                .ok_or_else(|| ParseError::at(input, line, "expected `x,y`"))?;
+            Ok((parse_token(input, x)?, parse_token(input, y)?))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
     let folds = input
         .lines()
         .skip(points.len() + 1)
-        .map(|line| match line.split_once("=").unwrap() {
-            ("fold along x", x) => Fold::X(x.parse().unwrap()),
-            ("fold along y", y) => Fold::Y(y.parse().unwrap()),
-            _ => unreachable!(),
+        .map(|line| match line.split_once("=") {
+            Some(("fold along x", x)) => Ok(Fold::X(parse_token(input, x)?)),
+            Some(("fold along y", y)) => Ok(Fold::Y(parse_token(input, y)?)),
+            _ => Err(ParseError::at(
+                input,
+                line,
+                "expected `fold along <x|y>=<n>`",
+            )),
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (points, folds)
+    if folds.is_empty() {
+        return Err(ParseError::eof(input, "expected at least one fold"));
+    }
+
+    Ok((points, folds))
 }
 
 fn transform(p: Point, fold: &Fold) -> Point {
@@ -41,46 +48,59 @@ fn transform(p: Point, fold: &Fold) -> Point {
     }
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let (points, folds) = read_input(input.unwrap_or(include_str!("../input.txt")));
-
-    points
-        .into_iter()
-        .map(|p| transform(p, &folds[0]))
-        .collect::<HashSet<_>>()
-        .len() as u64
+// the paper after the folds, a dot at (y, x) for each (x, y); render it from (0, 0) as
+// blank leading rows and columns are part of the letters
+fn fold_paper(points: &[Point], folds: &[Fold]) -> SparseGrid<bool> {
+    let mut paper = SparseGrid::new(false);
+    for &p in points {
+        let (x, y) = folds.iter().fold(p, transform);
+        paper.set((y as i64, x as i64), true);
+    }
+    paper
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let (points, folds) = read_input(input.unwrap_or(include_str!("../input.txt")));
+pub struct Day13;
 
-    let points = points
-        .into_iter()
-        .map(|p| folds.iter().fold(p, transform))
-        .collect::<HashSet<_>>();
+impl Solution for Day13 {
+    type Input = (Vec<Point>, Vec<Fold>);
 
-    let shape = (
-        points.iter().map(|p| p.0).max().unwrap(),
-        points.iter().map(|p| p.1).max().unwrap(),
-    );
+    const DAY: u8 = 13;
+    const NAME: &'static str = "Transparent Origami";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-    for y in 0..=shape.1 {
-        for x in 0..=shape.0 {
-            print!("{}", if points.contains(&(x, y)) { '#' } else { '.' })
-        }
-        println!();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a((points, folds): &Self::Input) -> Answer {
+        (fold_paper(points, &folds[..1]).len() as u64).into()
     }
 
-    0
+    fn part_b((points, folds): &Self::Input) -> Answer {
+        let text = fold_paper(points, folds)
+            .to_grid_from((0, 0))
+            .display_with(|&dot| if dot { '#' } else { '.' })
+            .to_string();
+
+        match decode_letters(&text) {
+            Some(letters) => Answer::Decoded(letters),
+            None => Answer::Text(text),
+        }
+    }
 }
 
+utils::example_tests!(Day13);
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
+    use super::Day13;
+    use utils::{solve, Part};
+
     #[test]
     fn test_folding_works() {
-        let (points, folds) = super::read_input(include_str!("../example.txt"));
+        let (points, folds) = super::read_input(include_str!("../examples/1.txt")).unwrap();
 
         let result = points
             .into_iter()
@@ -91,14 +111,14 @@ mod tests {
         assert_eq!(result, 16);
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 17);
-    }
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day13>(Part::A, None).unwrap();
         assert_eq!(result, 795);
     }
+    #[test]
+    fn test_part_b() {
+        let result = solve::<Day13>(Part::B, None).unwrap();
+        assert_eq!(result, "CEJKLUGJ");
+    }
 }
@@ -1,22 +1,29 @@
 use std::collections::HashMap;
+use utils::{Answer, ParseError, Solution};
 
 type InsertionRules = HashMap<(u8, u8), u8>;
 
-fn read_input(input: &str) -> (Vec<u8>, InsertionRules) {
+fn read_input(input: &str) -> Result<(Vec<u8>, InsertionRules), ParseError> {
     let mut lines = input.lines();
 
-    let seq = lines.next().unwrap().as_bytes().to_vec();
+    let seq = lines
+        .next()
+        .ok_or_else(|| ParseError::eof(input, "expected a polymer template"))?
+        .as_bytes()
+        .to_vec();
 
     let rules = lines
-        .into_iter()
         .skip(1)
         .map(|line| {
             let b = line.as_bytes();
-            ((b[0], b[1]), b[6])
+            if b.len() != 7 || &b[2..6] != b" -> " {
+                return Err(ParseError::at(input, line, "expected `AB -> C`"));
+            }
+            Ok(((b[0], b[1]), b[6]))
         })
-        .collect::<HashMap<_, _>>();
+        .collect::<Result<HashMap<_, _>, _>>()?;
 
-    (seq, rules)
+    Ok((seq, rules))
 }
 
 fn run_count(seq: &[u8], rules: &InsertionRules, n: usize) -> HashMap<u8, u64> {
@@ -49,25 +56,43 @@ fn run_count(seq: &[u8], rules: &InsertionRules, n: usize) -> HashMap<u8, u64> {
     counter
 }
 
-pub fn run_simulation(input: Option<&str>, n: usize) -> u64 {
-    let (seq, rules) = read_input(input.unwrap_or(include_str!("../input.txt")));
-    let counter = run_count(&seq, &rules, n);
+pub fn run_simulation(seq: &[u8], rules: &InsertionRules, n: usize) -> u64 {
+    let counter = run_count(seq, rules, n);
     counter.values().max().unwrap() - counter.values().min().unwrap()
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    run_simulation(input, 10)
-}
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = (Vec<u8>, InsertionRules);
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    run_simulation(input, 40)
+    const DAY: u8 = 14;
+    const NAME: &'static str = "Extended Polymerization";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a((seq, rules): &Self::Input) -> Answer {
+        run_simulation(seq, rules, 10).into()
+    }
+
+    fn part_b((seq, rules): &Self::Input) -> Answer {
+        run_simulation(seq, rules, 40).into()
+    }
 }
 
+utils::example_tests!(Day14);
+
 #[cfg(test)]
 mod tests {
+    use super::Day14;
+    use utils::{solve, Part};
+
     #[test]
     fn it_works() {
-        let (seq, rules) = super::read_input(include_str!("../example.txt"));
+        let (seq, rules) = super::read_input(include_str!("../examples/1.txt")).unwrap();
 
         assert_eq!(seq, b"NNCB");
         assert_eq!(rules.len(), 16);
@@ -77,27 +102,15 @@ mod tests {
         assert_eq!(super::run_count(&seq, &rules, 5).values().sum::<u64>(), 97);
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 1588);
-    }
-
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day14>(Part::A, None).unwrap();
         assert_eq!(result, 3230);
     }
 
-    #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 2188189693529);
-    }
-
     #[test]
     fn test_part_b() {
-        let result = super::part_b(None);
+        let result = solve::<Day14>(Part::B, None).unwrap();
         assert_eq!(result, 3542388214529);
     }
 }
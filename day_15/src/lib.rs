@@ -1,86 +1,103 @@
-use pathfinding::prelude::dijkstra;
-use utils::{Grid, Neighbors};
+use utils::search::dijkstra_buckets;
+use utils::{Answer, Grid, Neighbors, ParseError, Solution};
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let grid = Grid::<u8>::from_str(input.unwrap_or(include_str!("../input.txt")));
+fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse_with(input, |c| match c.to_digit(10) {
+        Some(risk @ 1..=9) => Ok(risk as u8),
+        _ => Err(format!("expected a risk level from 1 to 9, found `{}`", c)),
+    })?;
+    let (rows, cols) = grid.shape();
+    if rows == 0 || cols == 0 {
+        return Err(ParseError::eof(
+            input,
+            "expected a rectangle of risk levels",
+        ));
+    }
+    Ok(grid)
+}
 
-    let goal = (grid.shape.0 - 1, grid.shape.1 - 1);
-    let result = dijkstra(
-        &(0, 0),
+fn lowest_total_risk(grid: &Grid<u8>) -> u64 {
+    let (rows, cols) = grid.shape();
+    let goal = (rows - 1, cols - 1);
+    // risk levels only go from 1 to 9
+    let result = dijkstra_buckets(
+        (0, 0),
         |&(i, j)| {
-            grid.get_neighbors(i, j, Neighbors::Quadratic)
-                .into_iter()
-                .map(|(ii, jj)| ((ii, jj), *grid.get(ii, jj) as u64))
-                // .flatten()
-                .collect::<Vec<_>>()
+            grid.neighbor_values(i, j, Neighbors::Quadratic)
+                .map(|(p, &risk)| (p, risk as usize))
         },
         |p| *p == goal,
     );
 
-    result.unwrap().1
+    result.unwrap().0 as u64
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let grid = Grid::<u8>::from_str(input.unwrap_or(include_str!("../input.txt")));
-
-    let goal = ((grid.shape.0 * 5 - 1) as i32, (grid.shape.1 * 5 - 1) as i32);
-    let neighbors: Vec<(i32, i32)> = vec![(0, -1), (1, 0), (0, 1), (-1, 0)];
+fn lowest_total_risk_tiled(grid: &Grid<u8>) -> u64 {
+    // risks past 9 wrap back around to 1
+    let grid = grid.tiled((5, 5), |&risk, (ti, tj)| {
+        ((risk as usize + ti + tj - 1) % 9 + 1) as u8
+    });
+    let (rows, cols) = grid.shape();
+    let goal = (rows - 1, cols - 1);
 
-    let result = dijkstra(
-        &(0, 0),
+    let result = dijkstra_buckets(
+        (0, 0),
         |&(i, j)| {
-            neighbors
-                .iter()
-                .filter_map(|(di, dj)| {
-                    if i + di >= 0
-                        && i + di < (grid.shape.0 * 5) as i32
-                        && j + dj >= 0
-                        && j + dj < (grid.shape.1 * 5) as i32
-                    {
-                        Some(((i + di), (j + dj)))
-                    } else {
-                        None
-                    }
-                })
-                .map(|(ii, jj)| {
-                    let weight =
-                        *grid.get(ii as usize % grid.shape.0, jj as usize % grid.shape.1) as i32;
-                    // correction
-                    let weight = weight + ii / grid.shape.0 as i32 + jj / grid.shape.1 as i32 - 1;
-                    ((ii, jj), (weight % 9 + 1) as u64)
-                })
-                // .flatten()
-                .collect::<Vec<_>>()
+            grid.neighbor_values(i, j, Neighbors::Quadratic)
+                .map(|(p, risk)| (p, risk as usize))
         },
         |p| *p == goal,
     );
 
-    result.unwrap().1
+    result.unwrap().0 as u64
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Grid<u8>;
+
+    const DAY: u8 = 15;
+    const NAME: &'static str = "Chiton";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_a(grid: &Self::Input) -> Answer {
+        lowest_total_risk(grid).into()
+    }
+
+    fn part_b(grid: &Self::Input) -> Answer {
+        lowest_total_risk_tiled(grid).into()
+    }
 }
 
+utils::example_tests!(Day15);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 40);
-    }
+    use super::Day15;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day15>(Part::A, None).unwrap();
         assert_eq!(result, 604);
     }
 
     #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 315);
+    fn test_part_b() {
+        let result = solve::<Day15>(Part::B, None).unwrap();
+        assert_eq!(result, 2907);
     }
 
     #[test]
-    fn test_part_b() {
-        let result = super::part_b(None);
-        assert_eq!(result, 2907);
+    fn test_parse_input() {
+        assert!(super::parse_input("").is_err());
+        assert!(super::parse_input("\n").is_err());
+        let error = super::parse_input("19\n90").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
     }
 }
@@ -1,119 +1,148 @@
-use std::ops::ControlFlow;
+use std::ops::Range;
 
 use bitvec::prelude::*;
+use utils::{trace, Answer, Command, ParseError, Solution};
 
 const LITERAL_PACKET_TYPE_ID: u8 = 4;
 
 #[derive(Debug)]
-enum PacketValue {
+pub enum PacketValue {
     Literal(u64),
     Operator(Vec<Packet>),
 }
 
 #[derive(Debug)]
-struct Packet {
+pub struct Packet {
     version: u8,
     type_id: u8,
     value: PacketValue,
 }
 
-fn read_packet(bits: &BitSlice<Msb0, u8>) -> (Packet, usize) {
-    let version: u8 = bits[0..3].load_be();
-    let type_id: u8 = bits[3..6].load_be();
+// the bits in `range`, or an error naming what was expected if the transmission ends
+// before them
+fn take<'a>(
+    input: &str,
+    bits: &'a BitSlice<u8, Msb0>,
+    range: Range<usize>,
+    what: &str,
+) -> Result<&'a BitSlice<u8, Msb0>, ParseError> {
+    bits.get(range)
+        .ok_or_else(|| ParseError::eof(input, format!("expected {}, the transmission ended", what)))
+}
 
-    println!("New packet {}:{} [{}]", version, type_id, bits.len());
+fn read_packet(input: &str, bits: &BitSlice<u8, Msb0>) -> Result<(Packet, usize), ParseError> {
+    let version: u8 = take(input, bits, 0..3, "a packet version")?.load_be();
+    let type_id: u8 = take(input, bits, 3..6, "a packet type")?.load_be();
 
-    if type_id == LITERAL_PACKET_TYPE_ID {
-        let r = bits[6..]
-            .chunks(5)
-            .try_fold(BitVec::<Msb0, u8>::new(), |mut acc, x| {
-                acc.extend_from_bitslice(&x[1..5]);
-                if x[0] {
-                    ControlFlow::Continue(acc)
-                } else {
-                    ControlFlow::Break(acc)
-                }
-            });
+    trace!("New packet {}:{} [{}]", version, type_id, bits.len());
 
-        if let ControlFlow::Break(p) = r {
-            let length = 6 + p.len() + p.len() / 4;
-            return (
-                Packet {
-                    version,
-                    type_id,
-                    value: PacketValue::Literal(p.load_be()),
-                },
-                length,
-            );
-        } else {
-            unreachable!()
+    if type_id == LITERAL_PACKET_TYPE_ID {
+        let mut p = BitVec::<u8, Msb0>::new();
+        let mut start = 6;
+        loop {
+            let group = take(input, bits, start..start + 5, "a literal group")?;
+            p.extend_from_bitslice(&group[1..5]);
+            start += 5;
+            if !group[0] {
+                break;
+            }
+        }
+        if p.len() > 64 {
+            return Err(ParseError::eof(
+                input,
+                "expected a literal of at most 64 bits",
+            ));
         }
+
+        Ok((
+            Packet {
+                version,
+                type_id,
+                value: PacketValue::Literal(p.load_be()),
+            },
+            start,
+        ))
     } else {
-        let length_type_id: u8 = bits[6] as u8;
+        let length_type_id = take(input, bits, 6..7, "a length type")?[0];
 
-        if length_type_id == 0 {
-            let length: u16 = bits[7..22].load_be();
+        if !length_type_id {
+            let length: u16 = take(input, bits, 7..22, "a sub-packets length")?.load_be();
 
-            println!("{}", length);
+            trace!("sub-packets length: {}", length);
             let mut bits_read_total = 0;
             let mut sub_packets = Vec::new();
             loop {
-                let (p, bits_read) = read_packet(&bits[22 + bits_read_total..]);
-                println!("bits read: {}", bits_read);
+                let rest = take(
+                    input,
+                    bits,
+                    22 + bits_read_total..bits.len(),
+                    "a sub-packet",
+                )?;
+                let (p, bits_read) = read_packet(input, rest)?;
+                trace!("bits read: {}", bits_read);
                 sub_packets.push(p);
                 bits_read_total += bits_read;
                 if bits_read_total >= length.into() {
                     break;
                 }
             }
-            return (
+            Ok((
                 Packet {
                     version,
                     type_id,
                     value: PacketValue::Operator(sub_packets),
                 },
                 22 + bits_read_total,
-            );
+            ))
         } else {
-            let count: u16 = bits[7..18].load_be();
-            println!("{}", count);
+            let count: u16 = take(input, bits, 7..18, "a sub-packets count")?.load_be();
+            trace!("sub-packets count: {}", count);
 
             let mut bits_read_total = 0;
             let mut sub_packets = Vec::new();
 
             for _ in 0..count {
-                let (p, bits_read) = read_packet(&bits[18 + bits_read_total..]);
+                let rest = take(
+                    input,
+                    bits,
+                    18 + bits_read_total..bits.len(),
+                    "a sub-packet",
+                )?;
+                let (p, bits_read) = read_packet(input, rest)?;
                 sub_packets.push(p);
                 bits_read_total += bits_read;
             }
 
-            return (
+            Ok((
                 Packet {
                     version,
                     type_id,
                     value: PacketValue::Operator(sub_packets),
                 },
                 18 + bits_read_total,
-            );
+            ))
         }
     }
 }
 
-fn read_input(input: &str) -> BitVec<Msb0, u8> {
-    input.trim_end().chars().enumerate().fold(
-        BitVec::<Msb0, u8>::repeat(false, input.len() * 4),
+fn read_input(input: &str) -> Result<BitVec<u8, Msb0>, ParseError> {
+    input.trim_end().char_indices().try_fold(
+        BitVec::<u8, Msb0>::repeat(false, input.len() * 4),
         |mut bits, (i, x)| {
-            bits[i * 4..i * 4 + 4].store(x.to_digit(16).unwrap());
-            bits
+            let digit = x
+                .to_digit(16)
+                .ok_or_else(|| ParseError::at(input, &input[i..], "expected a hex digit"))?;
+            bits[i * 4..i * 4 + 4].store(digit);
+            Ok(bits)
         },
     )
 }
 
 fn count_versions(packet: &Packet, count: u64) -> u64 {
     match &packet.value {
-        PacketValue::Literal(_) => return count + packet.version as u64,
+        PacketValue::Literal(_) => count + packet.version as u64,
         PacketValue::Operator(sub_packets) => {
-            return count
+            count
                 + packet.version as u64
                 + sub_packets
                     .iter()
@@ -125,7 +154,7 @@ fn count_versions(packet: &Packet, count: u64) -> u64 {
 
 fn eval_packet(packet: &Packet) -> u64 {
     match &packet.value {
-        PacketValue::Literal(x) => return *x,
+        PacketValue::Literal(x) => *x,
         PacketValue::Operator(sub_packets) => match packet.type_id {
             0 => sub_packets.iter().map(eval_packet).sum::<u64>(),
             1 => sub_packets.iter().map(eval_packet).product::<u64>(),
@@ -139,23 +168,115 @@ fn eval_packet(packet: &Packet) -> u64 {
     }
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let bits = read_input(input.unwrap_or(include_str!("../input.txt")));
-    let (packet, _) = read_packet(&bits);
-    count_versions(&packet, 0)
+fn operator_name(type_id: u8) -> &'static str {
+    match type_id {
+        0 => "sum",
+        1 => "product",
+        2 => "minimum",
+        3 => "maximum",
+        4 => "literal",
+        5 => "greater than",
+        6 => "less than",
+        7 => "equal to",
+        _ => "unknown",
+    }
+}
+
+// `path` holds sub-packet indices separated by dots, eg `0.1`
+fn packet_at<'a>(packet: &'a Packet, path: &str) -> Result<&'a Packet, String> {
+    path.split('.')
+        .filter(|i| !i.is_empty())
+        .try_fold(packet, |packet, i| {
+            let sub_packets = match &packet.value {
+                PacketValue::Operator(sub_packets) => sub_packets,
+                PacketValue::Literal(_) => return Err("a literal has no sub-packets".to_string()),
+            };
+            i.parse::<usize>()
+                .ok()
+                .and_then(|i| sub_packets.get(i))
+                .ok_or_else(|| format!("no sub-packet `{}` in `{}`", i, path))
+        })
+}
+
+fn render(packet: &Packet, path: &str, lines: &mut Vec<String>) {
+    let depth = path.matches('.').count() + !path.is_empty() as usize;
+    lines.push(format!(
+        "{:indent$}{} v{} {} = {}",
+        "",
+        if path.is_empty() { "-" } else { path },
+        packet.version,
+        operator_name(packet.type_id),
+        eval_packet(packet),
+        indent = depth * 2
+    ));
+    if let PacketValue::Operator(sub_packets) = &packet.value {
+        for (i, sub_packet) in sub_packets.iter().enumerate() {
+            let path = match path {
+                "" => i.to_string(),
+                _ => format!("{}.{}", path, i),
+            };
+            render(sub_packet, &path, lines);
+        }
+    }
+}
+
+fn show(packet: &mut Packet, path: &str) -> Result<String, String> {
+    let mut lines = Vec::new();
+    render(packet_at(packet, path)?, path, &mut lines);
+    Ok(lines.join("\n"))
 }
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let bits = read_input(input.unwrap_or(include_str!("../input.txt")));
-    let (packet, _) = read_packet(&bits);
+fn eval(packet: &mut Packet, path: &str) -> Result<String, String> {
+    Ok(eval_packet(packet_at(packet, path)?).to_string())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+
+    const DAY: u8 = 16;
+    const NAME: &'static str = "Packet Decoder";
+    const INPUT: &'static str = include_str!("../input.txt");
+    const COMMANDS: &'static [Command<Self::Input>] = &[
+        Command {
+            name: "show",
+            help: "print the packet tree, or the sub-packet at a path like `0.1`",
+            run: show,
+        },
+        Command {
+            name: "eval",
+            help: "evaluate the packet, or the sub-packet at a path like `0.1`",
+            run: eval,
+        },
+    ];
 
-    eval_packet(&packet)
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let bits = read_input(input)?;
+        let (packet, _) = read_packet(input, &bits)?;
+        Ok(packet)
+    }
+
+    fn part_a(packet: &Self::Input) -> Answer {
+        count_versions(packet, 0).into()
+    }
+
+    fn part_b(packet: &Self::Input) -> Answer {
+        eval_packet(packet).into()
+    }
 }
 
+utils::example_tests!(Day16);
+
 #[cfg(test)]
 mod tests {
+    use super::{Day16, Packet, PacketValue};
+    use utils::{solve, Part, Solution};
 
-    use super::{Packet, PacketValue};
+    fn read(hex: &str) -> Packet {
+        let bits = super::read_input(hex).unwrap();
+        super::read_packet(hex, &bits).unwrap().0
+    }
 
     fn assert_literal_value(p: &Packet, x: u64) {
         match p.value {
@@ -165,13 +286,13 @@ mod tests {
     }
     #[test]
     fn test_literal_packet_works() {
-        let (packet, _) = super::read_packet(&super::read_input("D2FE28"));
+        let packet = read("D2FE28");
         assert_literal_value(&packet, 2021);
     }
 
     #[test]
     fn test_operator_packet_works_length_type_0() {
-        let (packet, _) = super::read_packet(&super::read_input("38006F45291200"));
+        let packet = read("38006F45291200");
         match packet.value {
             super::PacketValue::Operator(sub_packets) => {
                 assert_literal_value(&sub_packets[0], 10);
@@ -183,20 +304,33 @@ mod tests {
 
     #[test]
     fn test_operator_packet_works_length_type_1() {
-        let (packet, _) = super::read_packet(&super::read_input("EE00D40C823060"));
+        let packet = read("EE00D40C823060");
         match packet.value {
             super::PacketValue::Operator(sub_packets) => {
-                for i in 0..sub_packets.len() {
-                    assert_literal_value(&sub_packets[i], 1 + i as u64);
+                for (i, sub_packet) in sub_packets.iter().enumerate() {
+                    assert_literal_value(sub_packet, 1 + i as u64);
                 }
             }
             _ => panic!(),
         }
     }
 
+    #[test]
+    fn test_commands() {
+        let mut packet = read("C200B40A82");
+
+        assert_eq!(super::eval(&mut packet, "").unwrap(), "3");
+        assert_eq!(super::eval(&mut packet, "1").unwrap(), "2");
+        assert_eq!(
+            super::show(&mut packet, "").unwrap(),
+            "- v6 sum = 3\n  0 v6 literal = 1\n  1 v2 literal = 2"
+        );
+        assert!(super::eval(&mut packet, "1.0").is_err());
+    }
+
     #[test]
     fn test_nested() {
-        let (packet, _) = super::read_packet(&super::read_input("8A004A801A8002F478"));
+        let packet = read("8A004A801A8002F478");
 
         assert_eq!(packet.version, 4);
 
@@ -225,32 +359,19 @@ mod tests {
     }
 
     #[test]
-    fn test_part_a_works() {
-        assert_eq!(super::part_a(Some("8A004A801A8002F478")), 16);
-        assert_eq!(super::part_a(Some("620080001611562C8802118E34")), 12);
-        assert_eq!(super::part_a(Some("C0015000016115A2E0802F182340")), 23);
-        assert_eq!(super::part_a(Some("A0016C880162017C3686B18A3D4780")), 31);
+    fn test_truncated() {
+        for hex in ["", "8A0", "D2FE", "38006F4529", "EE00D40C82"] {
+            assert!(super::Day16::parse(hex).is_err(), "{:?} parsed", hex);
+        }
     }
 
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 893);
-    }
-
-    #[test]
-    fn test_part_b_works() {
-        assert_eq!(super::part_b(Some("C200B40A82")), 3);
-        assert_eq!(super::part_b(Some("04005AC33890")), 54);
-        assert_eq!(super::part_b(Some("880086C3E88112")), 7);
-        assert_eq!(super::part_b(Some("CE00C43D881120")), 9);
-        assert_eq!(super::part_b(Some("D8005AC2A8F0")), 1);
-        assert_eq!(super::part_b(Some("F600BC2D8F")), 0);
-        assert_eq!(super::part_b(Some("9C005AC2F8F0")), 0);
-        assert_eq!(super::part_b(Some("9C0141080250320F1802104A08")), 1);
+        assert_eq!(solve::<Day16>(Part::A, None).unwrap(), 893);
     }
 
     #[test]
     fn test_part_b() {
-        assert_eq!(super::part_b(None), 4358595186090);
+        assert_eq!(solve::<Day16>(Part::B, None).unwrap(), 4358595186090);
     }
 }
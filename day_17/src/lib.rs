@@ -1,3 +1,5 @@
+use utils::{parse_token, Answer, ParseError, Solution};
+
 // (x1, y1), (x2, y2)
 type Point = (i32, i32);
 type Area = (Point, Point);
@@ -29,7 +31,7 @@ fn probe(area: &Area, mut x_vel: i32, mut y_vel: i32) -> bool {
         }
         y_vel -= 1
     }
-    return false;
+    false
 }
 
 fn count_successful_probes(area: &Area) -> u64 {
@@ -40,38 +42,63 @@ fn count_successful_probes(area: &Area) -> u64 {
         .count() as u64
 }
 
-pub fn part_a(input: Option<Area>) -> u64 {
-    let ((_x1, y1), (_x2, y2)) = input.unwrap_or(((25, -260), (67, -200)));
-    (y1.min(y2) * (y1.min(y2) + 1) / 2) as u64
+fn read_input(input: &str) -> Result<Area, ParseError> {
+    let expected = "expected `target area: x=<a>..<b>, y=<c>..<d>`";
+    let (x, y) = input
+        .trim_end()
+        .trim_start_matches("target area: ")
+        .split_once(", ")
+        .ok_or_else(|| ParseError::at(input, input, expected))?;
+
+    let parse_range = |r: &str, axis: &str| -> Result<(i32, i32), ParseError> {
+        let (a, b) = r
+            .strip_prefix(axis)
+            .and_then(|r| r.split_once(".."))
+            .ok_or_else(|| ParseError::at(input, r, expected))?;
+        Ok((parse_token(input, a)?, parse_token(input, b)?))
+    };
+
+    let (x1, x2) = parse_range(x, "x=")?;
+    let (y1, y2) = parse_range(y, "y=")?;
+    Ok(((x1, y1), (x2, y2)))
 }
 
-pub fn part_b(input: Option<Area>) -> u64 {
-    count_successful_probes(&input.unwrap_or(((25, -260), (67, -200))))
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Area;
+
+    const DAY: u8 = 17;
+    const NAME: &'static str = "Trick Shot";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(&((_x1, y1), (_x2, y2)): &Self::Input) -> Answer {
+        ((y1.min(y2) * (y1.min(y2) + 1) / 2) as u64).into()
+    }
+
+    fn part_b(area: &Self::Input) -> Answer {
+        count_successful_probes(area).into()
+    }
 }
 
+utils::example_tests!(Day17);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        let area = ((20, -10), (30, -5));
-        let result = super::part_a(Some(area));
-        assert_eq!(result, 45);
-    }
+    use super::Day17;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 33670);
-    }
-
-    #[test]
-    fn test_part_b_works() {
-        let area = ((20, -10), (30, -5));
-        let result = super::part_b(Some(area));
-        assert_eq!(result, 112);
+        assert_eq!(solve::<Day17>(Part::A, None).unwrap(), 33670);
     }
 
     #[test]
     fn test_part_b() {
-        assert_eq!(super::part_b(None), 4903);
+        assert_eq!(solve::<Day17>(Part::B, None).unwrap(), 4903);
     }
 }
@@ -1,7 +1,8 @@
 use std::rc::Rc;
+use utils::{Answer, ParseError, Solution};
 
 #[derive(Debug, Clone)]
-enum Value {
+pub enum Value {
     Literal(u8),
     Pair(Rc<Value>, Rc<Value>),
 }
@@ -16,26 +17,52 @@ impl std::fmt::Display for Value {
 }
 
 impl std::str::FromStr for Value {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
+    // positions are relative to `s`, so errors always point at line 1
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(s.as_bytes()
-            .into_iter()
-            .fold(Vec::with_capacity(32), |mut stack, byte| {
-                match byte {
-                    // b'[' => stack.push(*byte),
-                    b'0'..=b'9' => stack.push(Value::Literal(byte - b'0')),
-                    b']' => {
-                        let right = stack.pop().unwrap();
-                        let left = stack.pop().unwrap();
-                        stack.push(Value::Pair(Rc::new(left), Rc::new(right)));
-                    }
-                    _ => {}
-                };
-                stack
-            })
-            .pop()
-            .unwrap())
+        let mut stack = Vec::with_capacity(32);
+        // one entry per open bracket, whether its `,` was seen yet
+        let mut open: Vec<bool> = Vec::with_capacity(16);
+        let mut expect_value = true;
+
+        for (i, byte) in s.bytes().enumerate() {
+            let error = |message| Err(ParseError::new(1, i + 1, message));
+            match (expect_value, byte, open.last()) {
+                (true, b'[', _) => open.push(false),
+                (true, b'0'..=b'9', _) => {
+                    stack.push(Value::Literal(byte - b'0'));
+                    expect_value = false;
+                }
+                (true, _, _) => return error("expected `[` or a digit"),
+                (false, _, None) => return error("expected the end of the number"),
+                (false, b',', Some(false)) => {
+                    *open.last_mut().unwrap() = true;
+                    expect_value = true;
+                }
+                (false, _, Some(false)) => return error("expected `,`"),
+                (false, b']', Some(true)) => {
+                    open.pop();
+                    let right = stack.pop().unwrap();
+                    let left = stack.pop().unwrap();
+                    stack.push(Value::Pair(Rc::new(left), Rc::new(right)));
+                }
+                (false, _, Some(true)) => return error("expected `]`"),
+            }
+        }
+
+        match (stack.pop(), expect_value || !open.is_empty()) {
+            (Some(value), false) => Ok(value),
+            _ => Err(ParseError::new(1, s.len() + 1, "expected a complete pair")),
+        }
+    }
+}
+
+impl std::ops::Add for Value {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::Pair(Rc::new(self), Rc::new(other)).reduce()
     }
 }
 
@@ -47,10 +74,6 @@ impl Value {
         }
     }
 
-    pub fn add(self, other: Self) -> Self {
-        Self::Pair(Rc::new(self), Rc::new(other)).reduce()
-    }
-
     fn reduce(mut self) -> Self {
         loop {
             if let Some(f) = self.explode() {
@@ -121,10 +144,10 @@ impl Value {
             Self::Pair(left, right) => {
                 if let Some(a) = left.split() {
                     Some(Self::Pair(Rc::new(a), Rc::clone(right)))
-                } else if let Some(b) = right.split() {
-                    Some(Self::Pair(Rc::clone(left), Rc::new(b)))
                 } else {
-                    None
+                    right
+                        .split()
+                        .map(|b| Self::Pair(Rc::clone(left), Rc::new(b)))
                 }
             }
             _ => None,
@@ -132,43 +155,57 @@ impl Value {
     }
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    input
-        .unwrap_or(include_str!("../input.txt"))
-        .lines()
-        .map(|line| line.parse::<Value>().unwrap())
-        .reduce(|acc, x| acc.add(x))
-        .unwrap()
-        .magnitude()
-}
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Value>;
+
+    const DAY: u8 = 18;
+    const NAME: &'static str = "Snailfish";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let fishes = input
-        .unwrap_or(include_str!("../input.txt"))
-        .lines()
-        .map(|line| line.parse::<Value>().unwrap())
-        .collect::<Vec<_>>();
-
-    let mut max = 0;
-
-    for i in 0..fishes.len() {
-        for j in 0..fishes.len() {
-            if i != j {
-                max = fishes[i]
-                    .clone()
-                    .add(fishes[j].clone())
-                    .magnitude()
-                    .max(max);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.parse::<Value>()
+                    .map_err(|e| ParseError::new(i + 1, e.column, e.message))
+            })
+            .collect()
+    }
+
+    fn part_a(fishes: &Self::Input) -> Answer {
+        fishes
+            .iter()
+            .cloned()
+            .reduce(|acc, x| acc + x)
+            .unwrap()
+            .magnitude()
+            .into()
+    }
+
+    fn part_b(fishes: &Self::Input) -> Answer {
+        let mut max = 0;
+
+        for i in 0..fishes.len() {
+            for j in 0..fishes.len() {
+                if i != j {
+                    max = (fishes[i].clone() + fishes[j].clone()).magnitude().max(max);
+                }
             }
         }
-    }
 
-    max
+        max.into()
+    }
 }
 
+utils::example_tests!(Day18);
+
 #[cfg(test)]
 mod tests {
-    use super::Value;
+    use super::{Day18, Value};
+    use utils::{solve, Part};
 
     #[test]
     fn it_works() {
@@ -188,6 +225,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let column = |s: &str| s.parse::<Value>().unwrap_err().column;
+        assert_eq!(column("[[1,2]"), 7);
+        assert_eq!(column("[1,2]]"), 6);
+        assert_eq!(column("[1[2]"), 3);
+        assert_eq!(column("[1,2,3]"), 5);
+        assert_eq!(column("[,1]"), 2);
+        assert_eq!(column(""), 1);
+    }
+
     #[test]
     fn test_magnitude() {
         let magnitude = "[[1,2],[[3,4],5]]".parse::<Value>().unwrap().magnitude();
@@ -217,27 +265,15 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part_a_works() {
-        let result = super::part_a(Some(include_str!("../example.txt")));
-        assert_eq!(result, 4140);
-    }
-
     #[test]
     fn test_part_a() {
-        let result = super::part_a(None);
+        let result = solve::<Day18>(Part::A, None).unwrap();
         assert_eq!(result, 3816);
     }
 
-    #[test]
-    fn test_part_b_works() {
-        let result = super::part_b(Some(include_str!("../example.txt")));
-        assert_eq!(result, 3993);
-    }
-
     #[test]
     fn test_part_b() {
-        let result = super::part_b(None);
+        let result = solve::<Day18>(Part::B, None).unwrap();
         assert_eq!(result, 4819);
     }
 }
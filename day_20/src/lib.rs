@@ -1,155 +1,165 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-type Range = (i64, i64);
+use utils::{debug, Answer, BitGrid, Grid, ParseError, Solution};
 
-struct Image {
-    pixels: HashSet<(i64, i64)>,
-    x_range: Range,
-    y_range: Range,
-    default: bool,
+// the pixels outside of `pixels` all share the `background` colour
+#[derive(Clone)]
+pub struct Image {
+    pixels: BitGrid,
+    background: bool,
 }
 
-impl Image {
-    fn is_relevant(&self, x: i64, y: i64) -> bool {
-        x >= self.x_range.0 && x <= self.x_range.1 && y >= self.y_range.0 && y <= self.y_range.1
+fn read_input(input: &str) -> Result<(Vec<bool>, Image), ParseError> {
+    let (algo, image) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::eof(input, "expected an empty line before the image"))?;
+    if let Some(i) = algo.find(|c| !"#.".contains(c)) {
+        return Err(ParseError::at(input, &algo[i..], "expected `#` or `.`"));
     }
-
-    fn iter_area(&self, x: i64, y: i64) -> impl Iterator<Item = bool> + '_ {
-        AREA.iter()
-            .map(move |(dx, dy)| (x + dx, y + dy))
-            .map(|(x, y)| {
-                if self.is_relevant(x, y) {
-                    self.pixels.contains(&(x, y))
-                } else {
-                    self.default
-                }
-            })
+    if algo.len() != 512 {
+        let message = format!("expected 512 pixels in the algorithm, found {}", algo.len());
+        return Err(ParseError::at(input, algo, message));
     }
-}
 
-fn read_input(input: Option<&str>) -> (HashSet<i64>, Image) {
-    let (algo, image) = input
-        .unwrap_or(include_str!("../input.txt"))
-        .split_once("\n\n")
-        .unwrap();
-
-    let is_lit = |(i, &x)| if x == b'#' { Some(i as i64) } else { None };
-
-    let algo = algo
-        .as_bytes()
-        .iter()
-        .enumerate()
-        .filter_map(is_lit)
-        .collect();
-
-    let n_rows = image.as_bytes().iter().filter(|&&x| x == b'\n').count() - 1;
-    let n_cols = image.split("\n").next().unwrap().len() - 1;
-
-    println!("{} {}", n_rows, n_cols);
-
-    let pixels = image
-        .split("\n")
-        .enumerate()
-        .flat_map(|(i, line)| {
-            line.as_bytes()
-                .iter()
-                .enumerate()
-                .filter_map(is_lit)
-                .map(move |j| (i as i64, j))
-        })
-        .collect();
-
-    (
+    let skipped = input[..input.len() - image.len()].lines().count();
+
+    let algo = algo.bytes().map(|x| x == b'#').collect();
+
+    let image = Grid::parse_with(image, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err("expected `#` or `.`".to_string()),
+    })
+    // the error points into the image, move it past the lines before it
+    .map_err(|e| ParseError::new(e.line + skipped, e.column, e.message))?;
+    let pixels = BitGrid::from_grid(&image);
+
+    debug!("image size: {:?}\n{}", pixels.shape(), pixels);
+
+    Ok((
         algo,
         Image {
             pixels,
-            x_range: (0, n_rows as i64),
-            y_range: (0, n_cols as i64),
-            default: false,
+            background: false,
         },
-    )
+    ))
 }
 
-const AREA: [(i64, i64); 9] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 0),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-fn bits_to_num(bits: impl Iterator<Item = bool>) -> i64 {
-    bits.fold(0, |acc, bit| (acc << 1) | (bit as i64))
+// the cells of `planes` whose bits, the first plane being the highest, make up the
+// number of an entry of `table` that is set, all of them `shape` big. The same
+// sub-tables come up again and again further down, so their results are memoised.
+fn select<'a>(
+    shape: (usize, usize),
+    planes: &[BitGrid],
+    table: &'a [bool],
+    memo: &mut HashMap<&'a [bool], BitGrid>,
+) -> BitGrid {
+    if let Some(cells) = memo.get(table) {
+        return cells.clone();
+    }
+
+    let (low, high) = table.split_at(table.len() / 2);
+    let cells = if table.iter().all(|&x| !x) {
+        BitGrid::new(shape.0, shape.1)
+    } else if table.iter().all(|&x| x) {
+        !&BitGrid::new(shape.0, shape.1)
+    } else if low == high {
+        select(shape, &planes[1..], low, memo)
+    } else {
+        let low = select(shape, &planes[1..], low, memo);
+        let high = select(shape, &planes[1..], high, memo);
+        &(&planes[0] & &high) | &(&!&planes[0] & &low)
+    };
+
+    memo.insert(table, cells.clone());
+    cells
 }
 
-fn enhance(algo: &HashSet<i64>, image: &Image) -> Image {
-    let pixels = ((image.x_range.0 - 1)..=(image.x_range.1 + 1))
-        .flat_map(|x| {
-            ((image.y_range.0 - 1)..=(image.y_range.1 + 1)).filter_map(move |y| {
-                let code = bits_to_num(image.iter_area(x, y));
-
-                if algo.contains(&code) {
-                    Some((x, y))
-                } else {
-                    None
-                }
-            })
-        })
-        .collect();
+// the image grows by a pixel on every side, past that the pixels only see the
+// background
+fn enhance(algo: &[bool], image: &Image) -> Image {
+    let (rows, cols) = image.pixels.shape();
+    // flipped so a set bit differs from the background, which makes the clear cells
+    // the shifts bring in from outside read as background
+    let flip = if image.background { 0b111111111 } else { 0 };
+    let pixels = if image.background {
+        !&image.pixels
+    } else {
+        image.pixels.clone()
+    };
+    let pixels = pixels.resize(rows + 2, cols + 2).shift(1, 1);
+
+    // one grid per bit of the window codes, from the top left neighbour on
+    let planes = (-1..=1)
+        .flat_map(|di| (-1..=1).map(move |dj| (di, dj)))
+        .map(|(di, dj)| pixels.shift(-di, -dj))
+        .collect::<Vec<_>>();
+    let table = (0..algo.len())
+        .map(|code| algo[code ^ flip])
+        .collect::<Vec<_>>();
 
     Image {
-        pixels,
-        x_range: (image.x_range.0 - 1, image.x_range.1 + 1),
-        y_range: (image.y_range.0 - 1, image.y_range.1 + 1),
-        default: algo.contains(if image.default { &0b111111111 } else { &0 }),
+        pixels: select(pixels.shape(), &planes, &table, &mut HashMap::new()),
+        background: algo[flip],
     }
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let (algo, mut image) = read_input(input);
+// only finite while the background is dark
+fn lit_pixels(image: &Image) -> u64 {
+    assert!(!image.background, "infinitely many pixels are lit");
+    image.pixels.count_ones()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = (Vec<bool>, Image);
 
-    for _i in 0..2 {
-        image = enhance(&algo, &image);
+    const DAY: u8 = 20;
+    const NAME: &'static str = "Trench Map";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
     }
 
-    image.pixels.len() as u64
-}
+    fn part_a((algo, image): &Self::Input) -> Answer {
+        let mut image = image.clone();
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    let (algo, mut image) = read_input(input);
+        for _i in 0..2 {
+            image = enhance(algo, &image);
+        }
 
-    for _i in 0..50 {
-        image = enhance(&algo, &image);
+        lit_pixels(&image).into()
     }
 
-    println!("{}", image.pixels.len());
+    fn part_b((algo, image): &Self::Input) -> Answer {
+        let mut image = image.clone();
 
-    image.pixels.len() as u64
+        for _i in 0..50 {
+            image = enhance(algo, &image);
+        }
+
+        debug!("lit pixels: {}", lit_pixels(&image));
+
+        lit_pixels(&image).into()
+    }
 }
 
+utils::example_tests!(Day20);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        assert_eq!(super::part_a(Some(include_str!("../example.txt"))), 35);
-    }
+    use super::Day20;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 5663);
-    }
-
-    #[test]
-    fn test_part_b_works() {
-        assert_eq!(super::part_b(Some(include_str!("../example.txt"))), 3351);
+        assert_eq!(solve::<Day20>(Part::A, None).unwrap(), 5663);
     }
 
     #[test]
     fn test_part_b() {
-        assert_eq!(super::part_b(None), 19638);
+        assert_eq!(solve::<Day20>(Part::B, None).unwrap(), 19638);
     }
 }
@@ -1,15 +1,36 @@
-pub fn part_a(input: Option<(u64, u64)>) -> u64 {
-    let (mut p1, mut p2) = input.unwrap_or((9, 3));
+use utils::{parse_token, trace, Answer, ParseError, Solution};
 
+fn read_input(input: &str) -> Result<(u64, u64), ParseError> {
+    let mut positions = input.lines().map(|line| {
+        let (_, position) = line.rsplit_once(": ").ok_or_else(|| {
+            ParseError::at(input, line, "expected `Player <n> starting position: <p>`")
+        })?;
+        match parse_token(input, position)? {
+            p @ 1..=10 => Ok(p),
+            _ => Err(ParseError::at(
+                input,
+                position,
+                "expected a position from 1 to 10",
+            )),
+        }
+    });
+
+    match (positions.next(), positions.next()) {
+        (Some(p1), Some(p2)) => Ok((p1?, p2?)),
+        _ => Err(ParseError::eof(input, "expected two players")),
+    }
+}
+
+fn play_deterministic(mut p1: u64, mut p2: u64) -> u64 {
     p1 -= 1;
     p2 -= 1;
 
     let mut p1_score = 0;
     let mut p2_score = 0;
 
-    for (i, s) in (1..u64::max_value()).step_by(3).enumerate() {
+    for (i, s) in (1..u64::MAX).step_by(3).enumerate() {
         let r = (s % 100) + (s + 1) % 100 + (s + 2) % 100;
-        println!("{} {} {}", s, s + 1, s + 2);
+        trace!("rolls: {} {} {}", s, s + 1, s + 2);
         if i % 2 == 0 {
             p1 = (p1 + r) % 10;
             p1_score += p1 + 1;
@@ -26,20 +47,45 @@ pub fn part_a(input: Option<(u64, u64)>) -> u64 {
     unreachable!();
 }
 
-pub fn part_b(input: Option<(u8, u8)>) -> u64 {
-    let (p1, p2) = input.unwrap_or((9, 3));
-    unimplemented!()
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = (u64, u64);
+
+    const DAY: u8 = 21;
+    const NAME: &'static str = "Dirac Dice";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(&(p1, p2): &Self::Input) -> Answer {
+        play_deterministic(p1, p2).into()
+    }
+
+    fn part_b(_input: &Self::Input) -> Answer {
+        unimplemented!()
+    }
 }
 
+utils::example_tests!(Day21, a);
+
 #[cfg(test)]
 mod tests {
+    use super::Day21;
+    use utils::{solve, Part};
+
     #[test]
-    fn test_part_a_works() {
-        assert_eq!(super::part_a(Some((4, 8))), 739785);
+    fn test_part_a() {
+        assert_eq!(solve::<Day21>(Part::A, None).unwrap(), 1073709);
     }
 
     #[test]
-    fn test_part_a() {
-        assert_eq!(super::part_a(None), 1073709);
+    fn test_parse_input() {
+        let input = "Player 1 starting position: 4\nPlayer 2 starting position: 0";
+        let error = super::read_input(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 29));
+        assert_eq!(error.message, "expected a position from 1 to 10");
     }
 }
@@ -1,97 +1,69 @@
-use utils::Grid;
+use utils::automaton::{Automaton, Neighborhood};
+use utils::{Answer, Boundary, Grid, Neighbors, ParseError, Solution};
 
-fn read_input(input: Option<&str>) -> Grid<u8> {
-    Grid::from_vec(
-        input
-            .unwrap_or(include_str!("../input.txt"))
-            .lines()
-            .map(|line| line.bytes().collect())
-            .collect(),
-    )
+const EAST: (i32, i32) = (0, 1);
+const SOUTH: (i32, i32) = (1, 0);
+
+fn read_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    let grid = Grid::parse_with(input, |c| match c {
+        '>' | 'v' | '.' => Ok(c as u8),
+        _ => Err("expected `>`, `v` or `.`".to_string()),
+    })?;
+
+    // herds leaving one edge reappear on the opposite one
+    Ok(grid.with_boundary(Boundary::Wrap))
 }
 
-fn apply_changes(grid: &mut Grid<u8>, changes: &Vec<(u8, usize, usize)>) {
-    for &(cell, i, j) in changes {
-        match cell {
-            b'>' => {
-                grid.set(i, j, b'.');
-                grid.set(i, (j + 1) % grid.shape.1, b'>');
-            }
-            b'v' => {
-                grid.set(i, j, b'.');
-                grid.set((i + 1) % grid.shape.0, j, b'v');
-            }
-            _ => {
-                unreachable!();
-            }
-        }
+// every cucumber of `herd` steps `d` ahead at once, if that cell was free before
+// any of them moved
+fn moves(herd: u8, (di, dj): (i32, i32)) -> impl Fn(&u8, &Neighborhood<u8>) -> u8 {
+    move |&cell, around| match cell {
+        _ if cell == herd && around.get((di, dj)) == Some(&b'.') => b'.',
+        b'.' if around.get((-di, -dj)) == Some(&herd) => herd,
+        _ => cell,
     }
 }
 
-pub fn part_a(input: Option<&str>) -> u64 {
-    let mut grid = read_input(input);
-    let mut changes = Vec::new();
-    let mut step = 0;
-    let mut changes_count = 0;
+fn steps_until_stable(grid: &Grid<u8>) -> u64 {
+    let mut automaton = Automaton::new(grid.clone(), Neighbors::Quadratic)
+        // the east facing herd moves first, then the south facing one
+        .phase(moves(b'>', EAST))
+        .phase(moves(b'v', SOUTH));
 
-    loop {
-        for i in 0..grid.shape.0 {
-            for j in 0..grid.shape.1 {
-                let cell = grid.get(i, j);
-                match cell {
-                    b'>' => {
-                        if *grid.get(i, (j + 1) % grid.shape.1) == b'.' {
-                            changes.push((b'>', i, j))
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+    automaton.run_until_stable() as u64
+}
 
-        apply_changes(&mut grid, &changes);
-        changes_count = changes.len();
-        changes.clear();
+pub struct Day25;
 
-        for i in 0..grid.shape.0 {
-            for j in 0..grid.shape.1 {
-                let cell = grid.get(i, j);
-                match cell {
-                    b'v' => {
-                        if *grid.get((i + 1) % grid.shape.0, j) == b'.' {
-                            changes.push((b'v', i, j))
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
+impl Solution for Day25 {
+    type Input = Grid<u8>;
 
-        apply_changes(&mut grid, &changes);
-        changes_count += changes.len();
-        changes.clear();
+    const DAY: u8 = 25;
+    const NAME: &'static str = "Sea Cucumber";
+    const INPUT: &'static str = include_str!("../input.txt");
 
-        step += 1;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
 
-        if changes_count == 0 {
-            return step;
-        }
+    fn part_a(grid: &Self::Input) -> Answer {
+        steps_until_stable(grid).into()
     }
-}
 
-pub fn part_b(input: Option<&str>) -> u64 {
-    unimplemented!()
+    fn part_b(_grid: &Self::Input) -> Answer {
+        unimplemented!()
+    }
 }
 
+utils::example_tests!(Day25, a);
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn test_part_a_works() {
-        assert_eq!(super::part_a(Some(include_str!("../example.txt"))), 58);
-    }
+    use super::Day25;
+    use utils::{solve, Part};
 
     #[test]
     fn test_part_a() {
-        assert_eq!(super::part_a(None), 300);
+        assert_eq!(solve::<Day25>(Part::A, None).unwrap(), 300);
     }
 }
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use utils::{Answer, Part};
+
+// answers are stored one per line, so multi-line text is escaped
+pub fn encode(answer: &Answer) -> String {
+    answer.to_string().replace('\n', "\\n")
+}
+
+// one `<day> <part> <input hash> <answer>` entry per line, `#` starts a comment
+#[derive(Default)]
+pub struct Answers {
+    entries: BTreeMap<(u8, Part, u64), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+
+        let mut answers = Self::default();
+
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let entry = Self::parse_entry(line)
+                .ok_or_else(|| format!("{}:{}: malformed entry", path.display(), i + 1))?;
+            answers.entries.insert(entry.0, entry.1);
+        }
+
+        Ok(answers)
+    }
+
+    fn parse_entry(line: &str) -> Option<((u8, Part, u64), String)> {
+        let mut fields = line.splitn(4, ' ');
+        let day = fields.next()?.parse().ok()?;
+        let part = match fields.next()? {
+            "a" => Part::A,
+            "b" => Part::B,
+            _ => return None,
+        };
+        let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+        let answer = fields.next()?.to_string();
+        Some(((day, part, hash), answer))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut content = String::from("# day part input-hash answer\n");
+        for ((day, part, hash), answer) in &self.entries {
+            content.push_str(&format!("{} {} {:016x} {}\n", day, part, hash, answer));
+        }
+        std::fs::write(path, content)
+    }
+
+    pub fn get(&self, day: u8, part: Part, hash: u64) -> Option<&str> {
+        self.entries.get(&(day, part, hash)).map(|x| x.as_str())
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, hash: u64, answer: String) {
+        self.entries.insert((day, part, hash), answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+    use utils::Part;
+
+    #[test]
+    fn test_parse_entry() {
+        let (key, answer) = Answers::parse_entry("16 b 00000000000000ff 4358595186090").unwrap();
+        assert_eq!(key, (16, Part::B, 255));
+        assert_eq!(answer, "4358595186090");
+
+        assert!(Answers::parse_entry("16 c 00000000000000ff 1").is_none());
+        assert!(Answers::parse_entry("16 b 00000000000000ff").is_none());
+    }
+}
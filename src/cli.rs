@@ -0,0 +1,127 @@
+use std::io::Read;
+use std::path::PathBuf;
+
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Self::File(path) => std::fs::read_to_string(path),
+        }
+    }
+}
+
+#[derive(Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Run,
+    Verify,
+    Watch,
+    Fetch,
+    New,
+    Repl,
+}
+
+#[derive(Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Default)]
+pub struct Args {
+    pub command: Command,
+    pub selection: Option<String>,
+    pub input: Option<InputSource>,
+    pub example: bool,
+    pub answers: Option<PathBuf>,
+    pub record: bool,
+    pub format: Format,
+    pub verbosity: u8,
+    pub time: bool,
+    pub repeat: Option<usize>,
+    pub jobs: Option<usize>,
+    pub url: Option<String>,
+    pub name: Option<String>,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-i" | "--input" => {
+                    let path = args.next().ok_or(format!("{} expects a path", arg))?;
+                    result.input = Some(input_source(path));
+                }
+                "--example" => result.example = true,
+                "--answers" => {
+                    let path = args.next().ok_or(format!("{} expects a path", arg))?;
+                    result.answers = Some(path.into());
+                }
+                "--record" => result.record = true,
+                "--name" => {
+                    result.name = Some(args.next().ok_or(format!("{} expects a name", arg))?)
+                }
+                "--url" => result.url = Some(args.next().ok_or(format!("{} expects a URL", arg))?),
+                "--format" => {
+                    result.format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        _ => return Err(format!("{} expects `text` or `json`", arg)),
+                    }
+                }
+                "--time" => result.time = true,
+                "--repeat" => {
+                    let n = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0);
+                    result.repeat = Some(n.ok_or(format!("{} expects a positive number", arg))?);
+                }
+                "-j" | "--jobs" => {
+                    let n = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0);
+                    result.jobs = Some(n.ok_or(format!("{} expects a positive number", arg))?);
+                }
+                "-v" => result.verbosity += 1,
+                "-vv" => result.verbosity += 2,
+                "-" => result.input = Some(InputSource::Stdin),
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{}`", arg)),
+                "verify" if result.command == Command::Run && result.selection.is_none() => {
+                    result.command = Command::Verify
+                }
+                "watch" if result.command == Command::Run && result.selection.is_none() => {
+                    result.command = Command::Watch
+                }
+                "fetch" if result.command == Command::Run && result.selection.is_none() => {
+                    result.command = Command::Fetch
+                }
+                "new" if result.command == Command::Run && result.selection.is_none() => {
+                    result.command = Command::New
+                }
+                "repl" if result.command == Command::Run && result.selection.is_none() => {
+                    result.command = Command::Repl
+                }
+                _ if result.selection.is_none() => result.selection = Some(arg),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn input_source(path: String) -> InputSource {
+    if path == "-" {
+        InputSource::Stdin
+    } else {
+        InputSource::File(path.into())
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::input_hash;
+
+pub const YEAR: u16 = 2021;
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// the site asks automated tools to say who to contact about them, `$AOC_USER_AGENT`
+// is the place for that
+pub const DEFAULT_USER_AGENT: &str = "aoc2021 input fetcher";
+
+#[derive(Debug)]
+pub enum FetchError {
+    // the server rejected the session cookie, usually because it expired
+    Unauthorized,
+    NotFound,
+    Http(String),
+    Io(std::io::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized => write!(f, "the session was rejected, log in again"),
+            Self::NotFound => write!(f, "no input for this day"),
+            Self::Http(e) => write!(f, "request failed: {}", e),
+            Self::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<std::io::Error> for FetchError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8, session: &str) -> Result<String, FetchError>;
+}
+
+// fetches `<base_url>/<year>/day/<day>/input` like the Advent of Code site serves it
+pub struct HttpFetcher {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: impl Into<String>, user_agent: &str) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(user_agent)
+                .build(),
+        }
+    }
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_URL, DEFAULT_USER_AGENT)
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: u16, day: u8, session: &str) -> Result<String, FetchError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call();
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            // the site answers 400 rather than 401 for a bad session
+            Err(ureq::Error::Status(400 | 401 | 403, _)) => Err(FetchError::Unauthorized),
+            Err(ureq::Error::Status(404, _)) => Err(FetchError::NotFound),
+            Err(e) => Err(FetchError::Http(e.to_string())),
+        }
+    }
+}
+
+// inputs differ per account, so they are cached under `<dir>/<year>/<session hash>/`;
+// only the hash ends up on disk, never the session itself
+pub struct InputStore<F> {
+    dir: PathBuf,
+    fetcher: F,
+}
+
+impl<F: Fetcher> InputStore<F> {
+    pub fn new(dir: impl Into<PathBuf>, fetcher: F) -> Self {
+        Self {
+            dir: dir.into(),
+            fetcher,
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8, session: &str) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("{:016x}", input_hash(session)))
+            .join(format!("day_{:02}.txt", day))
+    }
+
+    pub fn get(&self, year: u16, day: u8, session: &str) -> Result<String, FetchError> {
+        let path = self.path(year, day, session);
+        match std::fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+
+        let input = self.fetcher.fetch(year, day, session)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &input)?;
+        Ok(input)
+    }
+}
+
+// `$AOC_CACHE_DIR`, falling back to the platform cache directory
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("AOC_CACHE_DIR") {
+        return Some(dir.into());
+    }
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(&std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("aoc2021"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FetchError, Fetcher, HttpFetcher, InputStore, DEFAULT_USER_AGENT};
+    use crate::standin::StandInServer;
+    use std::cell::Cell;
+
+    struct Counting<'a>(&'a Cell<usize>);
+
+    impl Fetcher for Counting<'_> {
+        fn fetch(&self, _year: u16, day: u8, _session: &str) -> Result<String, FetchError> {
+            self.0.set(self.0.get() + 1);
+            Ok(day.to_string())
+        }
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc2021-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_store_caches_per_session() {
+        let calls = Cell::new(0);
+        let dir = temp_dir("store");
+        let store = InputStore::new(&dir, Counting(&calls));
+
+        assert_eq!(store.get(2021, 7, "alice").unwrap(), "7");
+        assert_eq!(store.get(2021, 7, "alice").unwrap(), "7");
+        assert_eq!(calls.get(), 1);
+
+        store.get(2021, 7, "bob").unwrap();
+        assert_eq!(calls.get(), 2);
+        assert_ne!(store.path(2021, 7, "alice"), store.path(2021, 7, "bob"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_fetcher() {
+        let server = StandInServer::start("secret", [(2021, 1, "199\n200\n".to_string())]).unwrap();
+        let fetcher = HttpFetcher::new(server.url(), DEFAULT_USER_AGENT);
+
+        assert_eq!(fetcher.fetch(2021, 1, "secret").unwrap(), "199\n200\n");
+        assert!(matches!(
+            fetcher.fetch(2021, 1, "expired"),
+            Err(FetchError::Unauthorized)
+        ));
+        assert!(matches!(
+            fetcher.fetch(2021, 2, "secret"),
+            Err(FetchError::NotFound)
+        ));
+    }
+}
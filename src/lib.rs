@@ -0,0 +1,56 @@
+pub mod inputs;
+#[cfg(test)]
+mod standin;
+
+use utils::Puzzle;
+
+pub const PUZZLES: &[&dyn Puzzle] = &[
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+    &day_06::Day06,
+    &day_07::Day07,
+    &day_08::Day08,
+    &day_09::Day09,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_18::Day18,
+    &day_20::Day20,
+    &day_21::Day21,
+    &day_22::Day22,
+    &day_24::Day24,
+    &day_25::Day25,
+];
+
+pub fn find_puzzle(day: u8) -> Option<&'static dyn Puzzle> {
+    PUZZLES.iter().find(|p| p.day() == day).copied()
+}
+
+// FNV-1a, stable across platforms and compiler versions unlike `DefaultHasher`
+pub fn input_hash(input: &str) -> u64 {
+    input
+        .trim_end()
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::input_hash;
+
+    #[test]
+    fn test_input_hash_ignores_trailing_whitespace() {
+        assert_eq!(input_hash("1\n2\n3"), input_hash("1\n2\n3\n"));
+        assert_ne!(input_hash("1\n2\n3"), input_hash("1\n2\n4"));
+    }
+}
@@ -1,52 +1,420 @@
-fn main() {
-    if let Some(selection) = std::env::args().nth(1) {
-        let result = match selection.as_str() {
-            "1a" => day_01::part_a(),
-            "1b" => day_01::part_b(),
-            "2a" => day_02::part_a(),
-            "2b" => day_02::part_b(),
-            "3a" => day_03::part_a(None),
-            "3b" => day_03::part_b(None),
-            "4a" => day_04::part_a(None),
-            "4b" => day_04::part_b(None),
-            "5a" => day_05::part_a(None),
-            "5b" => day_05::part_b(None),
-            "6a" => day_06::part_a(None),
-            "6b" => day_06::part_b(None),
-            "7a" => day_07::part_a(None),
-            "7b" => day_07::part_b(None),
-            "8a" => day_08::part_a(None),
-            "8b" => day_08::part_b(None),
-            "9a" => day_09::part_a(None),
-            "9b" => day_09::part_b(None),
-            "10a" => day_10::part_a(None),
-            "10b" => day_10::part_b(None),
-            "11a" => day_11::part_a(None),
-            "11b" => day_11::part_b(None),
-            "13a" => day_13::part_a(None),
-            "13b" => day_13::part_b(None),
-            "14a" => day_14::part_a(None),
-            "14b" => day_14::part_b(None),
-            "15a" => day_15::part_a(None),
-            "15b" => day_15::part_b(None),
-            "16a" => day_16::part_a(None),
-            "16b" => day_16::part_b(None),
-            "17a" => day_17::part_a(None),
-            "17b" => day_17::part_b(None),
-            "20a" => day_20::part_a(None),
-            "20b" => day_20::part_b(None),
-            "21a" => day_21::part_a(None),
-            "21b" => day_21::part_b(None),
-            "22a" => day_22::part_a(None),
-            "22b" => day_22::part_b(None),
-            "24a" => day_24::part_a(None),
-            "24b" => day_24::part_b(None),
-            "25a" => day_25::part_a(None),
-            "25b" => day_25::part_b(None),
-            _ => unimplemented!(),
+mod answers;
+mod cli;
+mod repl;
+mod runner;
+mod scaffold;
+mod watch;
+
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use answers::Answers;
+use aoc2021::inputs::{self, HttpFetcher, InputStore};
+use aoc2021::{find_puzzle, input_hash, PUZZLES};
+use cli::{Args, Command, Format, InputSource};
+use utils::{log, Part, Puzzle};
+
+enum Selection {
+    Part(u8, Part),
+    Days(RangeInclusive<u8>),
+}
+
+fn parse_selection(selection: &str) -> Option<Selection> {
+    if selection == "all" {
+        return Some(Selection::Days(1..=25));
+    }
+
+    if let Some((start, end)) = selection.split_once("..") {
+        let start = if start.is_empty() {
+            1
+        } else {
+            start.parse().ok()?
+        };
+        let end = match end.strip_prefix('=') {
+            Some(end) => end.parse().ok()?,
+            None if end.is_empty() => 25,
+            None => end.parse::<u8>().ok()?.checked_sub(1)?,
         };
-        println!("{}", result);
-    } else {
-        println!("Usage: <day><part>, eg `cargo run -- 1a`");
+        return Some(Selection::Days(start..=end));
+    }
+
+    if let Ok(day) = selection.parse() {
+        return Some(Selection::Days(day..=day));
+    }
+
+    let (day, part) = match selection.strip_suffix('a') {
+        Some(day) => (day, Part::A),
+        None => (selection.strip_suffix('b')?, Part::B),
+    };
+    Some(Selection::Part(day.parse().ok()?, part))
+}
+
+impl Selection {
+    fn parts(&self) -> Vec<(&'static dyn Puzzle, Part)> {
+        match self {
+            Self::Part(day, part) => find_puzzle(*day).map(|p| (p, *part)).into_iter().collect(),
+            Self::Days(days) => PUZZLES
+                .iter()
+                .filter(|p| days.contains(&p.day()))
+                .flat_map(|&p| [(p, Part::A), (p, Part::B)])
+                .collect(),
+        }
+    }
+}
+
+fn exit_with(message: String) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
+}
+
+fn single_day(parts: &[(&dyn Puzzle, Part)]) -> bool {
+    parts.windows(2).all(|w| w[0].0.day() == w[1].0.day())
+}
+
+fn run(selection: Selection, input: Option<&str>, format: Format, jobs: usize) {
+    if let (Selection::Part(day, part), Format::Text) = (&selection, &format) {
+        let puzzle = find_puzzle(*day)
+            .unwrap_or_else(|| exit_with(format!("Day {} is not implemented", day)));
+        let input = input.unwrap_or_else(|| puzzle.input());
+        let result = runner::silenced(|| {
+            panic::catch_unwind(AssertUnwindSafe(|| puzzle.run(*part, Some(input))))
+        });
+        match result {
+            Ok(Ok(answer)) => println!("{}", answer),
+            Ok(Err(e)) => {
+                eprintln!("{}", e.report(input));
+                std::process::exit(1);
+            }
+            // stdout only ever holds the answer, like a parse error this goes to stderr
+            Err(payload) => {
+                eprintln!(
+                    "error: panicked: {}",
+                    runner::panic_message(payload.as_ref())
+                );
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let parts = selection.parts();
+    if parts.is_empty() {
+        exit_with("No implemented day matches the selection".to_string());
+    }
+    if input.is_some() && !single_day(&parts) {
+        exit_with("A custom input can only be used with a single day".to_string());
+    }
+
+    let start = Instant::now();
+    let outcomes = runner::run_all(&parts, input, jobs);
+    match format {
+        Format::Text => runner::print_table(&outcomes, start.elapsed()),
+        Format::Json => runner::print_json(&outcomes),
+    }
+
+    if outcomes.iter().any(|o| o.result.is_err()) {
+        std::process::exit(1);
+    }
+}
+
+fn profile(selection: Selection, input: Option<&str>, repeat: usize) {
+    let parts = selection.parts();
+    if parts.is_empty() {
+        exit_with("No implemented day matches the selection".to_string());
+    }
+    if input.is_some() && !single_day(&parts) {
+        exit_with("A custom input can only be used with a single day".to_string());
+    }
+
+    let mut failed = false;
+    for (i, &(puzzle, part)) in parts.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match runner::profile(puzzle, part, input, repeat) {
+            Ok(profile) => runner::print_profile(puzzle, part, &profile),
+            Err(message) => {
+                println!(
+                    "{:>3} {:<4} {:<24} {}",
+                    puzzle.day(),
+                    part,
+                    puzzle.name(),
+                    message
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn verify(
+    selection: Selection,
+    input: Option<&str>,
+    answers_path: &Path,
+    record: bool,
+    jobs: usize,
+) {
+    let parts = selection.parts();
+    if input.is_some() && !single_day(&parts) {
+        exit_with("A custom input can only be used with a single day".to_string());
+    }
+
+    let mut answers = Answers::load(answers_path).unwrap_or_else(|e| exit_with(e));
+    let outcomes = runner::run_all(&parts, input, jobs);
+    let mut failed = 0;
+
+    for (outcome, (puzzle, _)) in outcomes.iter().zip(&parts) {
+        let hash = input_hash(input.unwrap_or_else(|| puzzle.input()));
+        let expected = answers.get(outcome.day, outcome.part, hash);
+
+        let status = match (&outcome.result, expected) {
+            (Err(message), Some(_)) => format!("FAILED {}", message),
+            (Err(_), None) => "not solved".to_string(),
+            (Ok(answer), Some(expected)) if answers::encode(answer) == expected => "ok".to_string(),
+            (Ok(answer), Some(expected)) => {
+                format!(
+                    "MISMATCH got {}, expected {}",
+                    answers::encode(answer),
+                    expected
+                )
+            }
+            (Ok(_), None) if record => "recorded".to_string(),
+            (Ok(_), None) => "no recorded answer".to_string(),
+        };
+
+        if status.starts_with("FAILED") || status.starts_with("MISMATCH") {
+            failed += 1;
+        }
+
+        if let (Ok(answer), None, true) = (&outcome.result, expected, record) {
+            answers.insert(outcome.day, outcome.part, hash, answers::encode(answer));
+        }
+
+        println!(
+            "{:>3} {:<4} {:<24} {:016x}  {}",
+            outcome.day, outcome.part, outcome.name, hash, status
+        );
+    }
+
+    if record {
+        answers
+            .save(answers_path)
+            .unwrap_or_else(|e| exit_with(format!("Failed to save answers: {}", e)));
+    }
+
+    println!("{} parts checked, {} failed", outcomes.len(), failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+// the first of the day's examples
+fn example_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("day_{:02}", day))
+        .join("examples")
+        .join("1.txt")
+}
+
+fn watch(selection: Selection, input: Option<InputSource>, example: bool) {
+    let (day, part) = match selection {
+        Selection::Part(day, part) => (day, part),
+        Selection::Days(_) => exit_with("watch expects a single part, eg `watch 22b`".to_string()),
+    };
+    if find_puzzle(day).is_none() {
+        exit_with(format!("Day {} is not implemented", day));
+    }
+
+    let input = match (input, example) {
+        (Some(InputSource::Stdin), _) => {
+            exit_with("watch can't read the input from stdin".to_string())
+        }
+        (Some(InputSource::File(path)), _) => Some(path),
+        (None, true) => Some(example_path(day)),
+        (None, false) => None,
+    };
+
+    let selection = format!("{}{}", day, part);
+    if let Err(e) = watch::watch(day, &selection, input) {
+        exit_with(format!("Failed to watch day {}: {}", day, e));
+    }
+}
+
+fn repl(selection: Selection, input: Option<&str>, example: bool) {
+    let day = match selection {
+        Selection::Days(days) if days.start() == days.end() => *days.start(),
+        _ => exit_with("repl expects a single day, eg `repl 16`".to_string()),
+    };
+    let puzzle =
+        find_puzzle(day).unwrap_or_else(|| exit_with(format!("Day {} is not implemented", day)));
+
+    let example = example.then(|| {
+        std::fs::read_to_string(example_path(day))
+            .unwrap_or_else(|e| exit_with(format!("Failed to read the example: {}", e)))
+    });
+    let input = example
+        .as_deref()
+        .or(input)
+        .unwrap_or_else(|| puzzle.input());
+
+    if let Err(e) = repl::repl(puzzle, input) {
+        eprintln!("{}", e.report(input));
+        std::process::exit(1);
+    }
+}
+
+// writes `input` to the day's input.txt, returns whether it changed
+fn populate(day: u8, input: &str) -> std::io::Result<bool> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("day_{:02}", day))
+        .join("input.txt");
+    if std::fs::read_to_string(&path).is_ok_and(|current| current == input) {
+        return Ok(false);
+    }
+    std::fs::write(path, input)?;
+    Ok(true)
+}
+
+fn fetch(selection: Selection, url: Option<String>) {
+    let session = std::env::var("AOC_SESSION").unwrap_or_else(|_| {
+        exit_with("Set AOC_SESSION to your adventofcode.com session cookie".to_string())
+    });
+    let dir = inputs::cache_dir()
+        .unwrap_or_else(|| exit_with("No cache directory found, set AOC_CACHE_DIR".to_string()));
+    let url = url
+        .or_else(|| std::env::var("AOC_URL").ok())
+        .unwrap_or_else(|| inputs::DEFAULT_URL.to_string());
+    let user_agent =
+        std::env::var("AOC_USER_AGENT").unwrap_or_else(|_| inputs::DEFAULT_USER_AGENT.to_string());
+    let store = InputStore::new(dir, HttpFetcher::new(url, &user_agent));
+
+    let mut puzzles = selection
+        .parts()
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect::<Vec<_>>();
+    puzzles.dedup_by_key(|p| p.day());
+    if puzzles.is_empty() {
+        exit_with("No implemented day matches the selection".to_string());
+    }
+
+    let mut failed = false;
+    for puzzle in puzzles {
+        let result = store
+            .get(inputs::YEAR, puzzle.day(), &session)
+            .map_err(|e| e.to_string())
+            .and_then(|input| populate(puzzle.day(), &input).map_err(|e| e.to_string()));
+        let status = match result {
+            Ok(true) => "updated".to_string(),
+            Ok(false) => "unchanged".to_string(),
+            Err(message) => {
+                failed = true;
+                format!("FAILED {}", message)
+            }
+        };
+        println!("{:>3} {:<24} {}", puzzle.day(), puzzle.name(), status);
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn new_day(selection: Selection, name: Option<String>) {
+    let day = match selection {
+        Selection::Days(days) if days.start() == days.end() && (1..=25).contains(days.start()) => {
+            *days.start()
+        }
+        _ => exit_with("new expects a single day, eg `new 19`".to_string()),
+    };
+    if find_puzzle(day).is_some() {
+        exit_with(format!("Day {} is already implemented", day));
+    }
+
+    let name = name.unwrap_or_else(|| format!("Day {}", day));
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let files = scaffold::new_day(root, day, &name).unwrap_or_else(|e| exit_with(e));
+
+    for file in files {
+        println!("{}", file.strip_prefix(root).unwrap_or(&file).display());
+    }
+    println!("fetch the input with `cargo run -- fetch {}`", day);
+}
+
+fn main() {
+    let args = Args::parse(std::env::args().skip(1)).unwrap_or_else(|e| exit_with(e));
+
+    if args.example && !matches!(args.command, Command::Watch | Command::Repl) {
+        exit_with("--example is only supported by watch and repl".to_string());
+    }
+    if args.example && args.input.is_some() {
+        exit_with("--example can't be combined with --input".to_string());
+    }
+    if let (Command::Repl, Some(InputSource::Stdin)) = (&args.command, &args.input) {
+        exit_with("repl can't read the input from stdin".to_string());
+    }
+
+    // watch passes its input on to every run instead of reading it once
+    let input = match (&args.command, &args.input) {
+        (Command::Watch, _) | (_, None) => None,
+        (_, Some(source)) => Some(
+            source
+                .read()
+                .unwrap_or_else(|e| exit_with(format!("Failed to read input: {}", e))),
+        ),
+    };
+
+    let selection = match args.selection.as_deref() {
+        Some(selection) => parse_selection(selection)
+            .unwrap_or_else(|| exit_with(format!("Invalid selection `{}`", selection))),
+        None if matches!(args.command, Command::Verify | Command::Fetch) => Selection::Days(1..=25),
+        None => {
+            println!(
+                "Usage: [verify] <day><part> | <day> | <from>..<to> | all [--input <path> | -] [--format text|json] [--time] [--repeat <n>] [--jobs <n>] [-v | -vv]"
+            );
+            println!("       watch <day><part> [--input <path> | --example]");
+            println!("       fetch [<day> | <from>..<to> | all] [--url <url>]");
+            println!("       repl <day> [--input <path> | --example]");
+            println!("       new <day> [--name <name>]");
+            println!("eg `cargo run -- 1a`, `cargo run -- verify all`, `cargo run -- watch 22b`");
+            return;
+        }
+    };
+
+    // only the selected days get verbose, so `all -v` and `16 -vv` behave as expected
+    let level = log::Level::from_verbosity(args.verbosity);
+    for (puzzle, _) in selection.parts() {
+        log::set_level(puzzle.day(), level);
+    }
+
+    let jobs = args.jobs.unwrap_or_else(runner::default_jobs);
+
+    match args.command {
+        Command::Run if args.time || args.repeat.is_some() => {
+            if args.format == Format::Json {
+                exit_with("--time only supports the text format".to_string())
+            }
+            profile(selection, input.as_deref(), args.repeat.unwrap_or(1))
+        }
+        Command::Run => run(selection, input.as_deref(), args.format, jobs),
+        Command::Verify if args.format == Format::Json => {
+            exit_with("--format json is only supported when running puzzles".to_string())
+        }
+        Command::Verify => {
+            let answers = args
+                .answers
+                .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.txt"));
+            verify(selection, input.as_deref(), &answers, args.record, jobs)
+        }
+        Command::Watch => watch(selection, args.input, args.example),
+        Command::Fetch => fetch(selection, args.url),
+        Command::New => new_day(selection, args.name),
+        Command::Repl => repl(selection, input.as_deref(), args.example),
     }
 }
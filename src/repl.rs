@@ -0,0 +1,95 @@
+use std::any::Any;
+use std::io::{BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use utils::{ParseError, Part, Puzzle};
+
+use crate::runner::{panic_message, silenced};
+
+const BUILTINS: &[(&str, &str)] = &[
+    ("a", "solve part a with the current state"),
+    ("b", "solve part b with the current state"),
+    ("reset", "parse the input again"),
+    ("help", "list the commands"),
+    ("quit", "leave the repl"),
+];
+
+// a command that panics shouldn't end the session
+fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    silenced(|| panic::catch_unwind(AssertUnwindSafe(f)))
+        .map_err(|payload| format!("panicked: {}", panic_message(payload.as_ref())))
+}
+
+fn help(puzzle: &dyn Puzzle) -> String {
+    let commands = puzzle.commands();
+    let width = commands
+        .iter()
+        .chain(BUILTINS)
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    commands
+        .iter()
+        .chain(BUILTINS)
+        .map(|(name, help)| format!("  {:w$}  {}", name, help, w = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn execute(puzzle: &dyn Puzzle, state: &mut Box<dyn Any>, line: &str) -> Result<String, String> {
+    let (name, args) = line.split_once(' ').unwrap_or((line, ""));
+    let args = args.trim();
+
+    match name {
+        "a" | "b" => {
+            let part = if name == "a" { Part::A } else { Part::B };
+            guarded(|| puzzle.solve(part, state.as_ref()).to_string())
+        }
+        "help" => Ok(help(puzzle)),
+        _ => guarded(|| puzzle.command(name, state.as_mut(), args))?
+            .unwrap_or_else(|| Err(format!("unknown command `{}`, try `help`", name))),
+    }
+}
+
+pub fn repl(puzzle: &dyn Puzzle, input: &str) -> Result<(), ParseError> {
+    let mut state = puzzle.parse(input)?;
+    let prompt = format!("day_{:02}> ", puzzle.day());
+
+    println!(
+        "day {}: {}, `help` lists the commands",
+        puzzle.day(),
+        puzzle.name()
+    );
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{}", prompt);
+        std::io::stdout().flush().ok();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        let line = line.trim();
+
+        match line {
+            "" => continue,
+            "quit" | "exit" => break,
+            "reset" => {
+                state = puzzle.parse(input)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        match execute(puzzle, &mut state, line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output),
+            Err(message) => println!("error: {}", message),
+        }
+    }
+
+    Ok(())
+}
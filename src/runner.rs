@@ -0,0 +1,287 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use utils::{Answer, ParseError, Part, Puzzle};
+
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    pub name: &'static str,
+    pub result: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+pub fn run(puzzle: &dyn Puzzle, part: Part, input: Option<&str>) -> Outcome {
+    let start = Instant::now();
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| puzzle.run(part, input))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err(format!("invalid input: {}", e)),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    };
+
+    Outcome {
+        day: puzzle.day(),
+        part,
+        name: puzzle.name(),
+        result,
+        elapsed: start.elapsed(),
+    }
+}
+
+// runs `f` with the default panic hook silenced,
+// so unfinished days end up in the output instead of on stderr
+pub fn silenced<T>(f: impl FnOnce() -> T) -> T {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+// recursive solutions need more than the 2MiB spawned threads get by default
+const STACK_SIZE: usize = 8 << 20;
+
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// runs the parts on `jobs` threads, the outcomes keep the order of `parts`
+pub fn run_all(parts: &[(&dyn Puzzle, Part)], input: Option<&str>, jobs: usize) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+
+    let mut outcomes = silenced(|| {
+        thread::scope(|scope| {
+            let workers = (0..jobs.clamp(1, parts.len().max(1)))
+                .map(|_| {
+                    thread::Builder::new()
+                        .stack_size(STACK_SIZE)
+                        .spawn_scoped(scope, || {
+                            let mut outcomes = Vec::new();
+                            loop {
+                                let i = next.fetch_add(1, Ordering::Relaxed);
+                                match parts.get(i) {
+                                    Some(&(puzzle, part)) => {
+                                        outcomes.push((i, run(puzzle, part, input)))
+                                    }
+                                    None => return outcomes,
+                                }
+                            }
+                        })
+                        .expect("failed to spawn a worker thread")
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("worker thread panicked"))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    outcomes.sort_by_key(|&(i, _)| i);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+pub struct Profile {
+    pub answer: Answer,
+    pub parse: Vec<Duration>,
+    pub solve: Vec<Duration>,
+}
+
+// parses and solves `repeat` times, timing both phases separately
+pub fn profile(
+    puzzle: &dyn Puzzle,
+    part: Part,
+    input: Option<&str>,
+    repeat: usize,
+) -> Result<Profile, String> {
+    let input = input.unwrap_or_else(|| puzzle.input());
+    let mut parse = Vec::with_capacity(repeat);
+    let mut solve = Vec::with_capacity(repeat);
+
+    let answers = silenced(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            (0..repeat)
+                .map(|_| {
+                    let start = Instant::now();
+                    let parsed = puzzle.parse(input)?;
+                    parse.push(start.elapsed());
+
+                    let start = Instant::now();
+                    let answer = puzzle.solve(part, parsed.as_ref());
+                    solve.push(start.elapsed());
+                    Ok(answer)
+                })
+                .collect::<Result<Vec<_>, ParseError>>()
+        }))
+    });
+
+    match answers {
+        Ok(Ok(mut answers)) => Ok(Profile {
+            answer: answers.pop().expect("at least one run"),
+            parse,
+            solve,
+        }),
+        Ok(Err(e)) => Err(format!("invalid input: {}", e)),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1e3)
+    } else {
+        format!("{:.2}s", micros as f64 / 1e6)
+    }
+}
+
+// one JSON object per line, so results can be streamed into other tools
+pub fn print_json(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        let (answer, error) = match &outcome.result {
+            Ok(Answer::Number(x)) => (x.to_string(), "null".to_string()),
+            Ok(answer) => (json_string(&answer.to_string()), "null".to_string()),
+            Err(message) => ("null".to_string(), json_string(message)),
+        };
+
+        let fields = [
+            ("day", outcome.day.to_string()),
+            ("part", json_string(&outcome.part.to_string())),
+            ("name", json_string(outcome.name)),
+            ("answer", answer),
+            ("duration_us", outcome.elapsed.as_micros().to_string()),
+            ("success", outcome.result.is_ok().to_string()),
+            ("error", error),
+        ];
+
+        let fields = fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\":{}", key, value))
+            .collect::<Vec<_>>();
+        println!("{{{}}}", fields.join(","));
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+// min, median and max
+fn stats(durations: &[Duration]) -> [Duration; 3] {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    [
+        sorted[0],
+        sorted[sorted.len() / 2],
+        sorted[sorted.len() - 1],
+    ]
+}
+
+pub fn print_profile(puzzle: &dyn Puzzle, part: Part, profile: &Profile) {
+    let runs = profile.parse.len();
+    println!(
+        "{:>3} {:<4} {:<24} {} {}",
+        puzzle.day(),
+        part,
+        puzzle.name(),
+        runs,
+        if runs == 1 { "run" } else { "runs" }
+    );
+    println!("{}", profile.answer);
+
+    let total = profile
+        .parse
+        .iter()
+        .zip(&profile.solve)
+        .map(|(parse, solve)| *parse + *solve)
+        .collect::<Vec<_>>();
+
+    println!("{:<8} {:>10} {:>10} {:>10}", "", "min", "median", "max");
+    for (phase, durations) in [
+        ("parse", &profile.parse),
+        ("solve", &profile.solve),
+        ("total", &total),
+    ] {
+        let [min, median, max] = stats(durations).map(format_duration);
+        println!("{:<8} {:>10} {:>10} {:>10}", phase, min, median, max);
+    }
+}
+
+pub fn print_table(outcomes: &[Outcome], wall_time: Duration) {
+    println!(
+        "{:>3} {:<4} {:<24} {:<16} {:>10}  Status",
+        "Day", "Part", "Name", "Answer", "Time"
+    );
+
+    for outcome in outcomes {
+        let (answer, status) = match &outcome.result {
+            Ok(answer) => (answer.to_string(), "ok".to_string()),
+            Err(message) => ("-".to_string(), message.clone()),
+        };
+
+        // multi-line answers continue below the answer column
+        let mut lines = answer.lines();
+        println!(
+            "{:>3} {:<4} {:<24} {:<16} {:>10}  {}",
+            outcome.day,
+            outcome.part,
+            outcome.name,
+            lines.next().unwrap_or_default(),
+            format_duration(outcome.elapsed),
+            status
+        );
+        for line in lines {
+            println!("{:34}{}", "", line);
+        }
+    }
+
+    let failed = outcomes.iter().filter(|o| o.result.is_err()).count();
+    let total: Duration = outcomes.iter().map(|o| o.elapsed).sum();
+    println!(
+        "{} parts, {} failed, {} total, {} wall time",
+        outcomes.len(),
+        failed,
+        format_duration(total),
+        format_duration(wall_time)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::json_string;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("Sonar Sweep"), r#""Sonar Sweep""#);
+        assert_eq!(json_string("#.\n\"x\"\\"), r##""#.\n\"x\"\\""##);
+        assert_eq!(json_string("\u{1b}"), r#""\u001b""#);
+    }
+}
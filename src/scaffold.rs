@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = r#"[package]
+name = "day_{day:02}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+utils = { path = "../utils" }
+"#;
+
+const LIB_RS: &str = r#"use utils::{Answer, ParseError, Solution};
+
+fn read_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {
+    type Input = Vec<String>;
+
+    const DAY: u8 = {day};
+    const NAME: &'static str = "{name}";
+    const INPUT: &'static str = include_str!("../input.txt");
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        read_input(input)
+    }
+
+    fn part_a(_input: &Self::Input) -> Answer {
+        unimplemented!()
+    }
+
+    fn part_b(_input: &Self::Input) -> Answer {
+        unimplemented!()
+    }
+}
+
+utils::example_tests!(Day{day:02});
+"#;
+
+const ANSWERS_TXT: &str = "# file part answer\n# 1.txt a <answer>\n";
+
+fn render(template: &str, day: u8, name: &str) -> String {
+    template
+        .replace("{day:02}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+        .replace("{name}", &name.escape_default().to_string())
+}
+
+// `line` uncommented, or inserted before the first line of the same kind that sorts
+// after it; lines of a kind start with `prefix` once comments are stripped
+fn insert_sorted(text: &str, line: &str, prefix: &str) -> Result<String, String> {
+    let key = |l: &str| l.trim().trim_start_matches('#').trim().to_string();
+
+    let mut lines = text.lines().map(String::from).collect::<Vec<_>>();
+    let kind = (0..lines.len())
+        .filter(|&i| key(&lines[i]).starts_with(prefix))
+        .collect::<Vec<_>>();
+    let last = *kind
+        .last()
+        .ok_or(format!("no `{}` entries found", prefix))?;
+
+    if let Some(&i) = kind.iter().find(|&&i| key(&lines[i]) == line) {
+        if !lines[i].trim().starts_with('#') {
+            return Err(format!("`{}` is already there", line));
+        }
+        let indent = lines[i].len() - lines[i].trim_start().len();
+        lines[i] = format!("{}{}", &lines[i][..indent], line);
+    } else {
+        let at = kind
+            .iter()
+            .copied()
+            .find(|&i| key(&lines[i]).as_str() > line)
+            .unwrap_or(last + 1);
+        let neighbour = &lines[at.min(last)];
+        let indent = &neighbour[..neighbour.len() - neighbour.trim_start().len()];
+        lines.insert(at, format!("{}{}", indent, line));
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn update(path: &Path, line: &str, prefix: &str) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let text =
+        insert_sorted(&text, line, prefix).map_err(|e| format!("{}: {}", path.display(), e))?;
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// writes the `day_XX` crate and registers it in the workspace, the dependencies and
+// `PUZZLES`, returning the files it touched
+pub fn new_day(root: &Path, day: u8, name: &str) -> Result<Vec<PathBuf>, String> {
+    let crate_name = format!("day_{:02}", day);
+    let dir = root.join(&crate_name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let files = [
+        (dir.join("Cargo.toml"), render(CARGO_TOML, day, name)),
+        (dir.join("src").join("lib.rs"), render(LIB_RS, day, name)),
+        (dir.join("input.txt"), String::new()),
+        (dir.join("examples").join("1.txt"), String::new()),
+        (
+            dir.join("examples").join("answers.txt"),
+            ANSWERS_TXT.to_string(),
+        ),
+    ];
+    for (path, content) in &files {
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| std::fs::write(path, content))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    let manifest = root.join("Cargo.toml");
+    let registry = root.join("src").join("lib.rs");
+    update(&manifest, &format!("\"{}\",", crate_name), "\"day_")?;
+    update(
+        &manifest,
+        &format!("{0} = {{ path = \"{0}\" }}", crate_name),
+        "day_",
+    )?;
+    update(
+        &registry,
+        &format!("&{}::Day{:02},", crate_name, day),
+        "&day_",
+    )?;
+
+    let mut touched = files.into_iter().map(|(path, _)| path).collect::<Vec<_>>();
+    touched.extend([manifest, registry]);
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::insert_sorted;
+
+    #[test]
+    fn test_insert_sorted() {
+        let members = "members = [\n    \"day_18\",\n    # \"day_19\",\n    \"day_20\",\n]\n";
+        assert_eq!(
+            insert_sorted(members, "\"day_19\",", "\"day_").unwrap(),
+            "members = [\n    \"day_18\",\n    \"day_19\",\n    \"day_20\",\n]\n"
+        );
+        assert_eq!(
+            insert_sorted(members, "\"day_21\",", "\"day_").unwrap(),
+            "members = [\n    \"day_18\",\n    # \"day_19\",\n    \"day_20\",\n    \"day_21\",\n]\n"
+        );
+        assert!(insert_sorted(members, "\"day_20\",", "\"day_").is_err());
+
+        let registry = "    &day_18::Day18,\n    &day_20::Day20,\n];\n";
+        assert_eq!(
+            insert_sorted(registry, "&day_19::Day19,", "&day_").unwrap(),
+            "    &day_18::Day18,\n    &day_19::Day19,\n    &day_20::Day20,\n];\n"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+// a minimal local stand-in for the Advent of Code input endpoint, so fetching can
+// be tested offline; it serves the given inputs to requests carrying `session`. It
+// only exists for the tests, `--url` or `$AOC_URL` point `fetch` at a real server
+pub struct StandInServer {
+    url: String,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StandInServer {
+    pub fn start(
+        session: &str,
+        inputs: impl IntoIterator<Item = (u16, u8, String)>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let inputs = inputs
+            .into_iter()
+            .map(|(year, day, input)| (format!("/{}/day/{}/input", year, day), input))
+            .collect::<HashMap<_, _>>();
+        let cookie = format!("session={}", session);
+
+        let handle = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // a broken client connection shouldn't take the server down
+                        let _ = respond(stream, &cookie, &inputs);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            url,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for StandInServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // wake up the blocking accept
+        let _ = TcpStream::connect(self.url.trim_start_matches("http://"));
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn respond(
+    mut stream: TcpStream,
+    cookie: &str,
+    inputs: &HashMap<String, String>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+
+    let mut authorized = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.trim_end().split_once(": ") {
+            if name.eq_ignore_ascii_case("cookie") {
+                authorized |= value.split("; ").any(|c| c == cookie);
+            }
+        }
+    }
+
+    let path = request.split(' ').nth(1).unwrap_or_default();
+    let (status, body) = match inputs.get(path) {
+        Some(input) if authorized => ("200 OK", input.as_str()),
+        // like the real site, which answers 400 to a missing or expired session
+        Some(_) => (
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in.",
+        ),
+        None => ("404 Not Found", "404 Not Found"),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::runner::format_duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// modification times of the given files, missing files included as `None`
+fn stamp(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| file.metadata().and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn source_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = dirs
+        .iter()
+        .filter_map(|dir| dir.read_dir().ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn rebuild(root: &Path) -> bool {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.arg("build").current_dir(root);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    println!("sources changed, rebuilding");
+    matches!(command.status(), Ok(status) if status.success())
+}
+
+// how `answer` differs from the previous run, multi-line answers are compared line by line
+fn describe_change(previous: Option<&str>, answer: &str) -> String {
+    match previous {
+        None => answer.to_string(),
+        Some(previous) if previous == answer => format!("{} (unchanged)", answer),
+        Some(previous) if !previous.contains('\n') && !answer.contains('\n') => {
+            format!("{} (was {})", answer, previous)
+        }
+        Some(previous) => {
+            let (old, new) = (
+                previous.lines().collect::<Vec<_>>(),
+                answer.lines().collect::<Vec<_>>(),
+            );
+            let mut result = String::from("(changed)");
+            for i in 0..old.len().max(new.len()) {
+                match (old.get(i), new.get(i)) {
+                    (Some(a), Some(b)) if a == b => result.push_str(&format!("\n  {}", a)),
+                    (a, b) => {
+                        if let Some(a) = a {
+                            result.push_str(&format!("\n- {}", a));
+                        }
+                        if let Some(b) = b {
+                            result.push_str(&format!("\n+ {}", b));
+                        }
+                    }
+                }
+            }
+            result
+        }
+    }
+}
+
+// runs the freshly built binary, so rebuilt code is picked up without restarting
+fn run(exe: &Path, selection: &str, input: Option<&Path>, previous: &mut Option<String>) {
+    let mut command = Command::new(exe);
+    command.arg(selection);
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+
+    let start = Instant::now();
+    let output = command.output();
+    let elapsed = start.elapsed();
+
+    match output {
+        Err(e) => println!("{}: failed to run {}: {}", selection, exe.display(), e),
+        // a day selection reports its failing parts in the table on stdout
+        Ok(output) if !output.status.success() => {
+            print!("{}", String::from_utf8_lossy(&output.stdout));
+            print!("{}", String::from_utf8_lossy(&output.stderr));
+            println!("{}: failed after {}", selection, format_duration(elapsed));
+        }
+        Ok(output) => {
+            let answer = String::from_utf8_lossy(&output.stdout)
+                .trim_end()
+                .to_string();
+            let change = describe_change(previous.as_deref(), &answer);
+            println!("{} [{}]: {}", selection, format_duration(elapsed), change);
+            *previous = Some(answer);
+        }
+    }
+}
+
+// `input` is a file passed to the part, without one the day's embedded input.txt is used
+pub fn watch(day: u8, selection: &str, input: Option<PathBuf>) -> std::io::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let day_dir = root.join(format!("day_{:02}", day));
+    let exe = std::env::current_exe()?;
+
+    // input.txt is embedded with `include_str!`, so it needs a rebuild too
+    let dirs = [day_dir.join("src"), root.join("utils").join("src")];
+    let sources = || {
+        let mut files = source_files(&dirs);
+        files.push(day_dir.join("input.txt"));
+        stamp(&files)
+    };
+    let outputs = [Some(exe.clone()), input.clone()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    println!("watching day {}, press Ctrl-C to stop", day);
+
+    let mut previous = None;
+    let mut last_sources = sources();
+    let mut last_outputs = None;
+
+    loop {
+        let sources_stamp = sources();
+        if sources_stamp != last_sources {
+            last_sources = sources_stamp;
+            if !rebuild(root) {
+                println!("build failed, waiting for changes");
+            }
+        }
+
+        let outputs_stamp = Some(stamp(&outputs));
+        if outputs_stamp != last_outputs {
+            last_outputs = outputs_stamp;
+            run(&exe, selection, input.as_deref(), &mut previous);
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::describe_change;
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(describe_change(None, "42"), "42");
+        assert_eq!(describe_change(Some("42"), "42"), "42 (unchanged)");
+        assert_eq!(describe_change(Some("41"), "42"), "42 (was 41)");
+        assert_eq!(
+            describe_change(Some("#.\n.#"), "#.\n##"),
+            "(changed)\n  #.\n- .#\n+ ##"
+        );
+    }
+}
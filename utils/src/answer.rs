@@ -0,0 +1,124 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    // free-form, possibly multi-line text such as a rendered dot matrix
+    Text(String),
+    // letters read back from a dot matrix, see `decode_letters`
+    Decoded(String),
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(x) => x.fmt(f),
+            Self::Text(s) | Self::Decoded(s) => f.pad(s),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(x: u64) -> Self {
+        Self::Number(x)
+    }
+}
+
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        matches!(self, Self::Number(x) if x == other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Text(s) | Self::Decoded(s) => s == other,
+        }
+    }
+}
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+// the 4x6 font used by the puzzles which draw their answer with `#` and `.`
+const LETTERS: [(char, &str); 18] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Y', "#...#....#.#..#...#...#."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+pub fn decode_letters(text: &str) -> Option<String> {
+    let rows = text.lines().collect::<Vec<_>>();
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(|row| row.len()).max()?;
+    // letters are separated by a single empty column
+    (0..(width + LETTER_WIDTH) / (LETTER_WIDTH + 1))
+        .map(|i| {
+            let glyph = rows
+                .iter()
+                .flat_map(|row| {
+                    let row = row.as_bytes();
+                    (0..LETTER_WIDTH).map(move |j| {
+                        let lit = row.get(i * (LETTER_WIDTH + 1) + j) == Some(&b'#');
+                        if lit {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                })
+                .collect::<String>();
+
+            LETTERS
+                .iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_letters, Answer};
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Answer::Number(42), 42);
+        assert_eq!(Answer::Decoded("HI".to_string()), "HI");
+        assert_ne!(Answer::Text("42".to_string()), 42);
+    }
+
+    #[test]
+    fn test_decode_letters() {
+        let text = [
+            "#..#..###",
+            "#..#...#.",
+            "####...#.",
+            "#..#...#.",
+            "#..#...#.",
+            "#..#..###",
+        ]
+        .join("\n");
+
+        assert_eq!(decode_letters(&text), Some("HI".to_string()));
+        assert_eq!(decode_letters("#\n#"), None);
+    }
+}
@@ -0,0 +1,213 @@
+use std::mem;
+
+use crate::{Grid, Neighbors};
+
+// A step runs the phases in order, every phase updating all the cells at once from
+// the grid the previous phase left behind: `phase` runs its rule once, `settle`
+// keeps running it until it stops changing anything. The grid's boundary policy
+// decides what the cells on the edges see.
+
+// what the rule of a cell gets to look at
+pub struct Neighborhood<'a, T> {
+    grid: &'a Grid<T>,
+    at: (usize, usize),
+    neighbors: Neighbors,
+}
+
+impl<'a, T> Neighborhood<'a, T> {
+    pub fn at(&self) -> (usize, usize) {
+        self.at
+    }
+
+    // the cell `d` away, if there's one
+    pub fn get(&self, d: (i32, i32)) -> Option<&'a T> {
+        self.grid
+            .neighbor(self.at.0, self.at.1, d)
+            .map(|p| &self.grid[p])
+    }
+
+    // the cells of the automaton's neighbourhood
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        let grid = self.grid;
+        grid.neighbors(self.at.0, self.at.1, self.neighbors)
+            .map(move |p| &grid[p])
+    }
+
+    pub fn count(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        self.iter().filter(|&cell| f(cell)).count()
+    }
+}
+
+type Rule<T> = Box<dyn Fn(&T, &Neighborhood<T>) -> T>;
+
+enum Phase<T> {
+    Once(Rule<T>),
+    Settle(Rule<T>),
+}
+
+pub struct Automaton<T> {
+    grid: Grid<T>,
+    // the next grid is written here, then the two are swapped
+    buffer: Grid<T>,
+    neighbors: Neighbors,
+    phases: Vec<Phase<T>>,
+    steps: usize,
+}
+
+impl<T: Clone + PartialEq> Automaton<T> {
+    pub fn new(grid: Grid<T>, neighbors: Neighbors) -> Self {
+        Self {
+            buffer: grid.clone(),
+            grid,
+            neighbors,
+            phases: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    pub fn phase(mut self, rule: impl Fn(&T, &Neighborhood<T>) -> T + 'static) -> Self {
+        self.phases.push(Phase::Once(Box::new(rule)));
+        self
+    }
+
+    pub fn settle(mut self, rule: impl Fn(&T, &Neighborhood<T>) -> T + 'static) -> Self {
+        self.phases.push(Phase::Settle(Box::new(rule)));
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    // how many steps were taken so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    // applies `rule` to every cell at once, returning how many of them changed
+    fn apply(
+        grid: &mut Grid<T>,
+        buffer: &mut Grid<T>,
+        neighbors: Neighbors,
+        rule: &Rule<T>,
+    ) -> usize {
+        let mut changed = 0;
+
+        for i in 0..grid.shape().0 {
+            let cells = grid.row(i).iter().zip(buffer.row_mut(i));
+            for (j, (cell, next)) in cells.enumerate() {
+                let neighborhood = Neighborhood {
+                    grid,
+                    at: (i, j),
+                    neighbors,
+                };
+                *next = rule(cell, &neighborhood);
+                if next != cell {
+                    changed += 1;
+                }
+            }
+        }
+
+        mem::swap(grid, buffer);
+        changed
+    }
+
+    // runs every phase once, returning how many cell updates its phases made in all; a
+    // cell changed by several phases counts once for each, and zero means nothing moved
+    pub fn step(&mut self) -> usize {
+        let mut changed = 0;
+
+        for phase in &self.phases {
+            match phase {
+                Phase::Once(rule) => {
+                    changed += Self::apply(&mut self.grid, &mut self.buffer, self.neighbors, rule);
+                }
+                Phase::Settle(rule) => loop {
+                    match Self::apply(&mut self.grid, &mut self.buffer, self.neighbors, rule) {
+                        0 => break,
+                        n => changed += n,
+                    }
+                },
+            }
+        }
+
+        self.steps += 1;
+        changed
+    }
+
+    // steps until one of them changes nothing, returning how many were taken including
+    // that last one
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.steps;
+        while self.step() > 0 {}
+        self.steps - start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Automaton;
+    use crate::{Grid, Neighbors};
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse_with(input, Ok).unwrap()
+    }
+
+    #[test]
+    fn test_simultaneous() {
+        // the blinker, which flips between a row and a column of three
+        let mut life = Automaton::new(
+            grid(".....\n..#..\n..#..\n..#..\n....."),
+            Neighbors::Diagonal,
+        )
+        .phase(|&cell, n| match (cell, n.count(|&c| c == '#')) {
+            (_, 3) | ('#', 2) => '#',
+            _ => '.',
+        });
+
+        assert_eq!(life.step(), 4);
+        assert_eq!(life.grid(), &grid(".....\n.....\n.###.\n.....\n....."));
+        life.step();
+        assert_eq!(life.grid(), &grid(".....\n..#..\n..#..\n..#..\n....."));
+        assert_eq!(life.steps(), 2);
+    }
+
+    #[test]
+    fn test_phases() {
+        // everything drifts right, and down after that, until it's stuck
+        let mut drift = Automaton::new(grid(">..\n.v.\n..v"), Neighbors::Quadratic)
+            .phase(|&cell, n| match cell {
+                '>' if n.get((0, 1)) == Some(&'.') => '.',
+                '.' if n.get((0, -1)) == Some(&'>') => '>',
+                _ => cell,
+            })
+            .phase(|&cell, n| match cell {
+                'v' if n.get((1, 0)) == Some(&'.') => '.',
+                '.' if n.get((-1, 0)) == Some(&'v') => 'v',
+                _ => cell,
+            });
+
+        assert_eq!(drift.step(), 4);
+        assert_eq!(drift.run_until_stable(), 2);
+        assert_eq!(drift.into_grid(), grid("..>\n...\n.vv"));
+    }
+
+    #[test]
+    fn test_settle() {
+        // a count that spreads until every cell holds the largest one
+        let mut spread = Automaton::new(
+            Grid::from_vec(vec![vec![1, 0, 0, 5, 0]]),
+            Neighbors::Quadratic,
+        )
+        .settle(|&cell, n| n.iter().copied().fold(cell, u8::max));
+
+        // 3 cells change on the first pass, then the 5 takes one more pass per cell to the left
+        assert_eq!(spread.step(), 5);
+        assert_eq!(spread.grid().row(0), &[5; 5]);
+        assert_eq!(spread.run_until_stable(), 1);
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::Grid;
+
+// a dense grid of booleans, 64 cells to a word; every row starts on a fresh word
+// with column j at bit j % 64, and the bits past the last column are kept clear
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    shape: (usize, usize),
+    stride: usize,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let stride = cols.div_ceil(64);
+        Self {
+            words: vec![0; rows * stride],
+            shape: (rows, cols),
+            stride,
+        }
+    }
+
+    pub fn from_grid(grid: &Grid<bool>) -> Self {
+        let (rows, cols) = grid.shape();
+        let mut bits = Self::new(rows, cols);
+        for ((i, j), &value) in grid.indexed_iter() {
+            bits.set(i, j, value);
+        }
+        bits
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    fn row(&self, i: usize) -> &[u64] {
+        &self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    fn row_mut(&mut self, i: usize) -> &mut [u64] {
+        &mut self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        assert!(
+            i < self.shape.0 && j < self.shape.1,
+            "({}, {}) is out of bounds",
+            i,
+            j
+        );
+        self.row(i)[j / 64] >> (j % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: bool) {
+        assert!(
+            i < self.shape.0 && j < self.shape.1,
+            "({}, {}) is out of bounds",
+            i,
+            j
+        );
+        let word = &mut self.row_mut(i)[j / 64];
+        if value {
+            *word |= 1 << (j % 64);
+        } else {
+            *word &= !(1 << (j % 64));
+        }
+    }
+
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    // clears the bits past the last column of every row
+    fn mask(&mut self) {
+        let rest = self.shape.1 % 64;
+        if rest == 0 {
+            return;
+        }
+        for last in self
+            .words
+            .iter_mut()
+            .skip(self.stride - 1)
+            .step_by(self.stride)
+        {
+            *last &= (1 << rest) - 1;
+        }
+    }
+
+    // the grid moved by (di, dj), so that (i, j) ends up at (i + di, j + dj); cells
+    // moved in from outside are clear
+    pub fn shift(&self, di: isize, dj: isize) -> Self {
+        let mut shifted = Self::new(self.shape.0, self.shape.1);
+        for i in 0..self.shape.0 {
+            let from = i as isize - di;
+            if from >= 0 && (from as usize) < self.shape.0 {
+                shift_words(self.row(from as usize), shifted.row_mut(i), dj);
+            }
+        }
+        shifted.mask();
+        shifted
+    }
+
+    // the grid cut down or padded with clear cells to `rows` x `cols`, (0, 0) staying put
+    pub fn resize(&self, rows: usize, cols: usize) -> Self {
+        let mut resized = Self::new(rows, cols);
+        let words = self.stride.min(resized.stride);
+        for i in 0..self.shape.0.min(rows) {
+            resized.row_mut(i)[..words].copy_from_slice(&self.row(i)[..words]);
+        }
+        resized.mask();
+        resized
+    }
+
+    // the 9 bit number read off the 3x3 window around (i, j) row by row, the top left
+    // cell being the highest bit; cells outside the grid read as `outside`
+    pub fn window(&self, i: isize, j: isize, outside: bool) -> usize {
+        (i - 1..=i + 1).fold(0, |code, i| code << 3 | self.triple(i, j - 1, outside))
+    }
+
+    // the cells (i, j), (i, j + 1) and (i, j + 2), the first one the highest bit
+    fn triple(&self, i: isize, j: isize, outside: bool) -> usize {
+        let (rows, cols) = (self.shape.0 as isize, self.shape.1 as isize);
+        let bits = if i < 0 || i >= rows {
+            if outside {
+                0b111
+            } else {
+                0
+            }
+        } else if j >= 0 && j + 3 <= cols {
+            // straight from the words, the three bits may straddle two of them
+            let (row, j) = (self.row(i as usize), j as usize);
+            let low = row[j / 64] >> (j % 64);
+            let high = match row.get(j / 64 + 1) {
+                Some(word) if j % 64 > 61 => word << (64 - j % 64),
+                _ => 0,
+            };
+            ((low | high) & 0b111) as usize
+        } else {
+            (0..3)
+                .map(|k| j + k)
+                .map(|j| {
+                    if j >= 0 && j < cols {
+                        self.get(i as usize, j as usize)
+                    } else {
+                        outside
+                    }
+                })
+                .enumerate()
+                .fold(0, |bits, (k, bit)| bits | (bit as usize) << k)
+        };
+
+        // low bit first to high bit first
+        (bits & 1) << 2 | (bits & 2) | (bits >> 2 & 1)
+    }
+
+    fn zip(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.shape, other.shape, "grids of different shapes");
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            shape: self.shape,
+            stride: self.stride,
+        }
+    }
+}
+
+// `dst` is `src` shifted towards the higher bits by `by` (or the lower bits if
+// negative), as if the words were one long number with the first word lowest
+fn shift_words(src: &[u64], dst: &mut [u64], by: isize) {
+    let (words, bits) = (by.unsigned_abs() / 64, by.unsigned_abs() % 64);
+    let word = |k: Option<usize>| k.and_then(|k| src.get(k)).copied().unwrap_or(0);
+
+    for (k, dst) in dst.iter_mut().enumerate() {
+        *dst = if by >= 0 {
+            let this = word(k.checked_sub(words));
+            let carry = word(k.checked_sub(words + 1));
+            match bits {
+                0 => this,
+                _ => this << bits | carry >> (64 - bits),
+            }
+        } else {
+            let this = word(Some(k + words));
+            let carry = word(Some(k + words + 1));
+            match bits {
+                0 => this,
+                _ => this >> bits | carry << (64 - bits),
+            }
+        };
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, other: &BitGrid) -> BitGrid {
+        self.zip(other, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut inverted = BitGrid {
+            words: self.words.iter().map(|word| !word).collect(),
+            shape: self.shape,
+            stride: self.stride,
+        };
+        inverted.mask();
+        inverted
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..self.shape.0 {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for j in 0..self.shape.1 {
+                write!(f, "{}", if self.get(i, j) { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitGrid;
+    use crate::Grid;
+
+    fn bits(input: &str) -> BitGrid {
+        BitGrid::from_grid(&Grid::parse_with(input, |c| Ok(c == '#')).unwrap())
+    }
+
+    #[test]
+    fn test_shift() {
+        let grid = bits("#..\n.#.\n..#");
+        assert_eq!(grid.shift(1, 0), bits("...\n#..\n.#."));
+        assert_eq!(grid.shift(0, -1), bits("...\n#..\n.#."));
+        assert_eq!(grid.shift(-1, 1), bits("..#\n...\n..."));
+
+        // across word boundaries
+        let mut wide = BitGrid::new(1, 150);
+        wide.set(0, 63, true);
+        wide.set(0, 149, true);
+        let shifted = wide.shift(0, 70);
+        assert_eq!((shifted.get(0, 133), shifted.count_ones()), (true, 1));
+        assert!(wide.shift(0, -62).get(0, 87));
+        assert!(wide.shift(0, -63).get(0, 0));
+
+        assert_eq!(grid.resize(2, 4), bits("#...\n.#.."));
+        let narrow = wide.resize(2, 100);
+        assert_eq!((narrow.get(0, 63), narrow.count_ones()), (true, 1));
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = bits("##.\n...");
+        let b = bits("#.#\n..#");
+        assert_eq!(&a & &b, bits("#..\n..."));
+        assert_eq!(&a | &b, bits("###\n..#"));
+        assert_eq!(&a ^ &b, bits(".##\n..#"));
+        assert_eq!((!&a).count_ones(), 4);
+    }
+
+    #[test]
+    fn test_window() {
+        let grid = bits("#..\n.#.\n..#");
+        assert_eq!(grid.window(1, 1, false), 0b100_010_001);
+        assert_eq!(grid.window(0, 0, false), 0b000_010_001);
+        assert_eq!(grid.window(0, 0, true), 0b111_110_101);
+        assert_eq!(grid.window(-5, 10, true), 0b111_111_111);
+
+        let mut wide = BitGrid::new(1, 130);
+        wide.set(0, 63, true);
+        wide.set(0, 64, true);
+        assert_eq!(wide.window(0, 63, false), 0b000_011_000);
+        assert_eq!(wide.window(0, 64, false), 0b000_110_000);
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // both 1-based
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    // `token` is expected to be a slice of `input`, so its position can be
+    // recovered from the pointers; anything else points at the end of the input
+    pub fn at(input: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len() && input.is_char_boundary(offset))
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+
+    pub fn eof(input: &str, message: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], message)
+    }
+
+    // the message followed by the offending line with a caret under the column
+    pub fn report(&self, input: &str) -> String {
+        let mut result = format!("error: {}", self);
+        if let Some(line) = input.lines().nth(self.line - 1) {
+            let number = self.line.to_string();
+            result.push_str(&format!(
+                "\n{:w$} |\n{} | {}\n{:w$} | {:>c$}",
+                "",
+                number,
+                line,
+                "",
+                "^",
+                w = number.len(),
+                c = self.column
+            ));
+        }
+        result
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// parses a slice of `input`, pointing at it when it isn't a valid `T`
+pub fn parse_token<T>(input: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| ParseError::at(input, token, format!("invalid value `{}`: {}", token, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_token, ParseError};
+
+    #[test]
+    fn test_position() {
+        let input = "forward 5\ndown x\n";
+        let token = &input[15..16];
+
+        assert_eq!(ParseError::at(input, token, "").line, 2);
+        assert_eq!(ParseError::at(input, token, "").column, 6);
+        assert_eq!(ParseError::eof(input, "").line, 3);
+        assert_eq!(parse_token::<u8>(input, &input[8..9]), Ok(5));
+    }
+
+    #[test]
+    fn test_report() {
+        let input = "forward 5\ndown x";
+        let error = parse_token::<u8>(input, &input[15..]).unwrap_err();
+
+        assert_eq!(
+            error.report(input),
+            [
+                "error: line 2, column 6: invalid value `x`: invalid digit found in string",
+                "  |",
+                "2 | down x",
+                "  |      ^",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use crate::{Part, Solution};
+
+// one `<file> <part> <answer>` entry per line of `examples/answers.txt`, `#` starts a
+// comment; multi-line answers are written with `\n` like in the top level answers.txt
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub file: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Example>, String> {
+    manifest
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let mut fields = line.splitn(3, ' ');
+            let (file, part, answer) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(file), Some("a"), Some(answer)) => (file, Part::A, answer),
+                (Some(file), Some("b"), Some(answer)) => (file, Part::B, answer),
+                _ => return Err(format!("line {}: expected `<file> <a|b> <answer>`", i + 1)),
+            };
+            Ok(Example {
+                file: file.to_string(),
+                part,
+                answer: answer.replace("\\n", "\n"),
+            })
+        })
+        .collect()
+}
+
+// runs every example of `part` listed in `<dir>/examples/answers.txt`, reporting all
+// the mismatches at once rather than stopping at the first; a part without any
+// examples fails too, so a missing entry can't pass unnoticed
+pub fn check<S: Solution>(dir: &str, part: Part) {
+    let dir = Path::new(dir).join("examples");
+    let manifest = std::fs::read_to_string(dir.join("answers.txt"))
+        .unwrap_or_else(|e| panic!("{}: {}", dir.join("answers.txt").display(), e));
+    let examples = parse_manifest(&manifest).unwrap_or_else(|e| panic!("answers.txt {}", e));
+
+    let examples = examples
+        .iter()
+        .filter(|example| example.part == part)
+        .collect::<Vec<_>>();
+    assert!(
+        !examples.is_empty(),
+        "no part {} examples in {}",
+        part,
+        dir.join("answers.txt").display()
+    );
+
+    let failures = examples
+        .into_iter()
+        .filter_map(|example| {
+            let input = std::fs::read_to_string(dir.join(&example.file))
+                .unwrap_or_else(|e| panic!("{}: {}", example.file, e));
+            let answer = match S::parse(&input) {
+                Ok(input) if part == Part::A => S::part_a(&input).to_string(),
+                Ok(input) => S::part_b(&input).to_string(),
+                Err(e) => return Some(format!("{}: {}", example.file, e)),
+            };
+            (answer != example.answer).then(|| {
+                format!(
+                    "{}: expected {}, got {}",
+                    example.file, example.answer, answer
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// generates a test per part that checks the answers of the day's examples, both
+// parts unless they're listed; `ignore = "<reason>"` marks a day that has none, or
+// when followed by parts, ones whose examples don't pass yet
+#[macro_export]
+macro_rules! example_tests {
+    (@part $solution:ident, a $(, #[$attr:meta])?) => {
+        #[test]
+        $(#[$attr])?
+        fn part_a() {
+            $crate::examples::check::<$solution>(env!("CARGO_MANIFEST_DIR"), $crate::Part::A);
+        }
+    };
+    (@part $solution:ident, b $(, #[$attr:meta])?) => {
+        #[test]
+        $(#[$attr])?
+        fn part_b() {
+            $crate::examples::check::<$solution>(env!("CARGO_MANIFEST_DIR"), $crate::Part::B);
+        }
+    };
+    ($solution:ident, ignore = $reason:literal) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[test]
+            #[ignore = $reason]
+            fn examples() {}
+        }
+    };
+    ($solution:ident, ignore = $reason:literal, $($part:ident),+) => {
+        #[cfg(test)]
+        mod example_tests {
+            use super::$solution;
+
+            $($crate::example_tests!(@part $solution, $part, #[ignore = $reason]);)+
+        }
+    };
+    ($solution:ident, $($part:ident),+) => {
+        #[cfg(test)]
+        mod example_tests {
+            use super::$solution;
+
+            $($crate::example_tests!(@part $solution, $part);)+
+        }
+    };
+    ($solution:ident) => {
+        $crate::example_tests!($solution, a, b);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_manifest, Example};
+    use crate::Part;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "# file part answer\n1.txt a 7\n\n2.txt b #.\\n.#\n";
+        assert_eq!(
+            parse_manifest(manifest),
+            Ok(vec![
+                Example {
+                    file: "1.txt".to_string(),
+                    part: Part::A,
+                    answer: "7".to_string(),
+                },
+                Example {
+                    file: "2.txt".to_string(),
+                    part: Part::B,
+                    answer: "#.\n.#".to_string(),
+                },
+            ])
+        );
+        assert_eq!(
+            parse_manifest("1.txt c 7"),
+            Err("line 1: expected `<file> <a|b> <answer>`".to_string())
+        );
+        assert!(parse_manifest("1.txt a").is_err());
+    }
+}
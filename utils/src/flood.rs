@@ -0,0 +1,134 @@
+use crate::{Grid, Neighbors};
+
+// a set of connected cells, in the order they were reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub cells: Vec<(usize, usize)>,
+    // inclusive top left and bottom right corners
+    pub bbox: ((usize, usize), (usize, usize)),
+}
+
+impl Component {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl<T> Grid<T> {
+    // the passable cells reachable from `start`, `None` if it isn't passable itself
+    pub fn flood_fill<F>(
+        &self,
+        start: (usize, usize),
+        neighbors: Neighbors,
+        mut passable: F,
+    ) -> Option<Component>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let (rows, cols) = self.shape();
+        let mut seen = Grid::new(rows, cols, false);
+        self.fill(start, neighbors, &mut passable, &mut seen)
+    }
+
+    // every component of passable cells, and the grid of the component each cell
+    // belongs to, as an index into them
+    pub fn label_components<F>(
+        &self,
+        neighbors: Neighbors,
+        mut passable: F,
+    ) -> (Grid<Option<usize>>, Vec<Component>)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let (rows, cols) = self.shape();
+        let mut seen = Grid::new(rows, cols, false);
+        let mut labels = Grid::new(rows, cols, None);
+        let mut components = Vec::new();
+
+        for i in 0..rows {
+            for j in 0..cols {
+                if seen[(i, j)] {
+                    continue;
+                }
+                if let Some(component) = self.fill((i, j), neighbors, &mut passable, &mut seen) {
+                    for &p in &component.cells {
+                        labels[p] = Some(components.len());
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        (labels, components)
+    }
+
+    // a depth first walk with an explicit stack, so large areas can't overflow the
+    // call stack; `seen` is shared so components can be collected one after the other
+    fn fill<F>(
+        &self,
+        start: (usize, usize),
+        neighbors: Neighbors,
+        passable: &mut F,
+        seen: &mut Grid<bool>,
+    ) -> Option<Component>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if seen[start] || !passable(&self[start]) {
+            return None;
+        }
+
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut component = Component {
+            cells: Vec::new(),
+            bbox: (start, start),
+        };
+
+        while let Some((i, j)) = stack.pop() {
+            component.cells.push((i, j));
+            let ((i0, j0), (i1, j1)) = component.bbox;
+            component.bbox = ((i0.min(i), j0.min(j)), (i1.max(i), j1.max(j)));
+
+            for next in self.neighbors(i, j, neighbors) {
+                if !seen[next] && passable(&self[next]) {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        Some(component)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Boundary, Grid, Neighbors};
+
+    #[test]
+    fn test_components() {
+        let grid = "1190\n1909\n9901".parse::<Grid<u8>>().unwrap();
+        let low = |&x: &u8| x != 9;
+
+        let basin = grid.flood_fill((0, 0), Neighbors::Quadratic, low).unwrap();
+        assert_eq!((basin.size(), basin.bbox), (3, ((0, 0), (1, 1))));
+        assert_eq!(grid.flood_fill((0, 2), Neighbors::Quadratic, low), None);
+
+        let (labels, components) = grid.label_components(Neighbors::Quadratic, low);
+        let sizes = components.iter().map(|c| c.size()).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![3, 1, 3]);
+        assert_eq!(components[2].bbox, ((1, 2), (2, 3)));
+        assert_eq!(
+            (labels[(0, 3)], labels[(2, 3)], labels[(1, 1)]),
+            (Some(1), Some(2), None)
+        );
+
+        // across corners, or over the edges once they wrap, it's all one area
+        let (_, components) = grid.label_components(Neighbors::Diagonal, low);
+        assert_eq!(components.len(), 1);
+        let grid = grid.with_boundary(Boundary::Wrap);
+        let (_, components) = grid.label_components(Neighbors::Quadratic, low);
+        assert_eq!(components.len(), 1);
+    }
+}
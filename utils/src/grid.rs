@@ -0,0 +1,471 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbors {
+    Quadratic,
+    Diagonal,
+    Custom(&'static [(i32, i32)]),
+}
+
+const QUADRATIC: &[(i32, i32)] = &[(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const DIAGONAL: &[(i32, i32)] = &[
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+impl Neighbors {
+    pub fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Neighbors::Quadratic => QUADRATIC,
+            Neighbors::Diagonal => DIAGONAL,
+            Neighbors::Custom(offsets) => offsets,
+        }
+    }
+}
+
+// what lies past the edges: nothing, or the opposite edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Boundary {
+    #[default]
+    Bounded,
+    Wrap,
+}
+
+impl Boundary {
+    fn resolve(self, (rows, cols): (usize, usize), i: isize, j: isize) -> Option<(usize, usize)> {
+        match self {
+            Boundary::Bounded => {
+                let i = usize::try_from(i).ok().filter(|&i| i < rows)?;
+                let j = usize::try_from(j).ok().filter(|&j| j < cols)?;
+                Some((i, j))
+            }
+            Boundary::Wrap if rows == 0 || cols == 0 => None,
+            Boundary::Wrap => Some((wrap(i, rows), wrap(j, cols))),
+        }
+    }
+
+    fn neighbors(
+        self,
+        shape: (usize, usize),
+        i: usize,
+        j: usize,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        neighbors.offsets().iter().filter_map(move |&(di, dj)| {
+            self.resolve(shape, i as isize + di as isize, j as isize + dj as isize)
+        })
+    }
+}
+
+// `i` taken modulo `n`, skipping the division for the usual case of being at most one
+// lap off
+fn wrap(i: isize, n: usize) -> usize {
+    let n = n as isize;
+    if (0..n).contains(&i) {
+        i as usize
+    } else if (-n..0).contains(&i) {
+        (i + n) as usize
+    } else {
+        i.rem_euclid(n) as usize
+    }
+}
+
+// row-major, cell (i, j) lives at `data[i * shape.1 + j]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    shape: (usize, usize),
+    boundary: Boundary,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; rows * cols],
+            shape: (rows, cols),
+            boundary: Boundary::Bounded,
+        }
+    }
+
+    // panics if the rows have different lengths
+    pub fn from_vec(data: Vec<Vec<T>>) -> Self {
+        let shape = (data.len(), data.first().map_or(0, |row| row.len()));
+        assert!(
+            data.iter().all(|row| row.len() == shape.1),
+            "every row must have {} columns",
+            shape.1
+        );
+
+        Self {
+            data: data.into_iter().flatten().collect(),
+            shape,
+            boundary: Boundary::Bounded,
+        }
+    }
+
+    // a column past the end would silently land on the next row
+    fn offset(&self, i: usize, j: usize) -> usize {
+        assert!(j < self.shape.1, "column {} is out of bounds", j);
+        i * self.shape.1 + j
+    }
+
+    pub fn with_boundary(self, boundary: Boundary) -> Self {
+        Self { boundary, ..self }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    // the cell that (i, j) stands for under the boundary policy, if any
+    pub fn resolve(&self, i: isize, j: isize) -> Option<(usize, usize)> {
+        self.boundary.resolve(self.shape, i, j)
+    }
+
+    pub fn lookup(&self, i: isize, j: isize) -> Option<&T> {
+        self.resolve(i, j).map(|p| &self[p])
+    }
+
+    // the cell `d` away from (i, j)
+    pub fn neighbor(&self, i: usize, j: usize, (di, dj): (i32, i32)) -> Option<(usize, usize)> {
+        self.resolve(i as isize + di as isize, j as isize + dj as isize)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        &self[(i, j)]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        self[(i, j)] = value
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.shape.1..(i + 1) * self.shape.1]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.shape.1..(i + 1) * self.shape.1]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, an empty grid has no rows either way
+        self.data.chunks(self.shape.1.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut()
+    }
+
+    // every cell with its (row, col)
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.shape.1;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(k, x)| ((k / cols, k % cols), x))
+    }
+
+    // the cells around (i, j) under the boundary policy; the iterator only holds on to
+    // the shape, so the grid can be modified while walking it
+    pub fn neighbors(
+        &self,
+        i: usize,
+        j: usize,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.boundary.neighbors(self.shape, i, j, neighbors)
+    }
+
+    pub fn neighbor_values(
+        &self,
+        i: usize,
+        j: usize,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(i, j, neighbors)
+            .map(move |(ni, nj)| ((ni, nj), &self[(ni, nj)]))
+    }
+}
+
+impl<T> Grid<T> {
+    // the grid repeated `tiles` times in each direction, the copy at tile (ti, tj)
+    // holding `transform(value, (ti, tj))`; the boundary policy applies to the whole
+    pub fn tiled<F>(&self, tiles: (usize, usize), transform: F) -> Tiled<'_, T, F>
+    where
+        F: Fn(&T, (usize, usize)) -> T,
+    {
+        Tiled {
+            grid: self,
+            tiles,
+            transform,
+        }
+    }
+}
+
+pub struct Tiled<'a, T, F> {
+    grid: &'a Grid<T>,
+    tiles: (usize, usize),
+    transform: F,
+}
+
+impl<T, F> Tiled<'_, T, F>
+where
+    F: Fn(&T, (usize, usize)) -> T,
+{
+    pub fn shape(&self) -> (usize, usize) {
+        (
+            self.grid.shape.0 * self.tiles.0,
+            self.grid.shape.1 * self.tiles.1,
+        )
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T {
+        let (rows, cols) = self.grid.shape;
+        assert!(i < rows * self.tiles.0 && j < cols * self.tiles.1);
+        (self.transform)(&self.grid[(i % rows, j % cols)], (i / rows, j / cols))
+    }
+
+    pub fn resolve(&self, i: isize, j: isize) -> Option<(usize, usize)> {
+        self.grid.boundary.resolve(self.shape(), i, j)
+    }
+
+    pub fn lookup(&self, i: isize, j: isize) -> Option<T> {
+        self.resolve(i, j).map(|(i, j)| self.get(i, j))
+    }
+
+    pub fn neighbors(
+        &self,
+        i: usize,
+        j: usize,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.grid.boundary.neighbors(self.shape(), i, j, neighbors)
+    }
+
+    pub fn neighbor_values(
+        &self,
+        i: usize,
+        j: usize,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = ((usize, usize), T)> + '_ {
+        self.neighbors(i, j, neighbors)
+            .map(move |(ni, nj)| ((ni, nj), self.get(ni, nj)))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[self.offset(i, j)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        let offset = self.offset(i, j);
+        &mut self.data[offset]
+    }
+}
+
+impl<T> Grid<T> {
+    // one cell per character, one row per line; errors point at the offending character
+    // or at the first line whose length differs from the first one
+    pub fn parse_with<F>(input: &str, mut parse: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let mut data = Vec::with_capacity(input.len());
+        let mut shape = (0, 0);
+
+        for line in input.lines() {
+            for (i, c) in line.char_indices() {
+                let cell =
+                    parse(c).map_err(|message| ParseError::at(input, &line[i..], message))?;
+                data.push(cell);
+            }
+
+            let cols = data.len() - shape.0 * shape.1;
+            if shape.0 == 0 {
+                shape.1 = cols;
+            } else if cols != shape.1 {
+                let message = format!("expected {} columns, found {}", shape.1, cols);
+                return Err(ParseError::at(input, line, message));
+            }
+            shape.0 += 1;
+        }
+
+        Ok(Self {
+            data,
+            shape,
+            boundary: Boundary::Bounded,
+        })
+    }
+
+    // `render` turns every cell into what gets printed for it
+    pub fn display_with<F, D>(&self, render: F) -> Rendered<'_, T, F>
+    where
+        F: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        Rendered { grid: self, render }
+    }
+}
+
+pub struct Rendered<'a, T, F> {
+    grid: &'a Grid<T>,
+    render: F,
+}
+
+fn write_rows<T>(
+    grid: &Grid<T>,
+    f: &mut fmt::Formatter,
+    mut write_cell: impl FnMut(&T, &mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    for (i, row) in grid.rows().enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+        for cell in row {
+            write_cell(cell, f)?;
+        }
+    }
+    Ok(())
+}
+
+impl<T, F, D> fmt::Display for Rendered<'_, T, F>
+where
+    F: Fn(&T) -> D,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(self.grid, f, |cell, f| write!(f, "{}", (self.render)(cell)))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_rows(self, f, |cell, f| write!(f, "{}", cell))
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, |c| match c.to_digit(10) {
+            Some(x) => Ok(x as u8),
+            None => Err(format!("expected a digit, found `{}`", c)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boundary, Grid, Neighbors};
+
+    #[test]
+    fn test_layout() {
+        let mut grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        grid[(1, 0)] = 7;
+
+        assert_eq!(grid.shape(), (2, 3));
+        assert_eq!(grid[(0, 2)], 3);
+        assert_eq!(grid.row(1), &[7, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.indexed_iter().nth(4), Some(((1, 1), &5)));
+        assert_eq!(Grid::new(2, 2, 0), Grid::from_vec(vec![vec![0; 2]; 2]));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(
+            grid.neighbors(0, 0, Neighbors::Quadratic)
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors(1, 1, Neighbors::Diagonal).count(), 5);
+        assert_eq!(
+            grid.neighbor_values(1, 2, Neighbors::Custom(&[(0, -2), (-1, 0), (0, 1)]))
+                .collect::<Vec<_>>(),
+            vec![((1, 0), &4), ((0, 2), &3)]
+        );
+    }
+
+    #[test]
+    fn test_boundary() {
+        let grid = Grid::from_vec(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(grid.lookup(-1, 0), None);
+        assert_eq!(grid.neighbor(1, 2, (0, 1)), None);
+
+        let grid = grid.with_boundary(Boundary::Wrap);
+        assert_eq!(grid.lookup(-1, 0), Some(&4));
+        assert_eq!(grid.neighbor(1, 2, (0, 1)), Some((1, 0)));
+        assert_eq!(grid.neighbors(0, 0, Neighbors::Diagonal).count(), 8);
+    }
+
+    #[test]
+    fn test_tiled() {
+        let grid = Grid::from_vec(vec![vec![1, 2], vec![3, 4]]);
+        let tiled = grid.tiled((2, 3), |&x, (ti, tj)| x + 10 * ti + 100 * tj);
+
+        assert_eq!(tiled.shape(), (4, 6));
+        assert_eq!(tiled.get(3, 5), 214);
+        assert_eq!(tiled.lookup(4, 0), None);
+        assert_eq!(
+            tiled
+                .neighbor_values(1, 1, Neighbors::Quadratic)
+                .collect::<Vec<_>>(),
+            vec![((1, 0), 3), ((2, 1), 12), ((1, 2), 103), ((0, 1), 2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_with() {
+        let parse = |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("expected `#` or `.`, found `{}`", c)),
+        };
+
+        let grid = Grid::parse_with("#..\n.##\n", parse).unwrap();
+        assert_eq!(grid.shape(), (2, 3));
+        assert!(grid[(1, 2)]);
+        assert_eq!(
+            grid.display_with(|&lit| if lit { '#' } else { '.' })
+                .to_string(),
+            "#..\n.##"
+        );
+
+        let error = Grid::parse_with("#..\n.#x\n", parse).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        let error = Grid::parse_with("#..\n.#\n...\n", parse).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        assert_eq!("12\n34".parse::<Grid<u8>>().unwrap().to_string(), "12\n34");
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn from_verbosity(verbosity: u8) -> Self {
+        match verbosity {
+            0 => Self::Off,
+            1 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::Off => "off",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        })
+    }
+}
+
+// index 0 is used for everything that isn't a `day_XX` crate
+static LEVELS: [AtomicU8; 26] = [const { AtomicU8::new(0) }; 26];
+// highest level of all days, checked first so disabled logging stays cheap in hot loops
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+fn slot(target: &str) -> usize {
+    target
+        .split("::")
+        .next()
+        .and_then(|name| name.strip_prefix("day_"))
+        .and_then(|day| day.parse::<usize>().ok())
+        .filter(|&day| day < LEVELS.len())
+        .unwrap_or(0)
+}
+
+pub fn set_level(day: u8, level: Level) {
+    if let Some(slot) = LEVELS.get(day as usize) {
+        slot.store(level as u8, Ordering::Relaxed);
+        MAX_LEVEL.fetch_max(level as u8, Ordering::Relaxed);
+    }
+}
+
+pub fn level(day: u8) -> Level {
+    LEVELS.get(day as usize).map_or(Level::Off, |slot| {
+        Level::from_verbosity(slot.load(Ordering::Relaxed))
+    })
+}
+
+pub fn enabled(target: &str, level: Level) -> bool {
+    level != Level::Off
+        && level as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+        && level as u8 <= LEVELS[slot(target)].load(Ordering::Relaxed)
+}
+
+pub fn write(target: &str, level: Level, args: std::fmt::Arguments) {
+    let target = target.split("::").next().unwrap_or(target);
+    eprintln!("[{} {}] {}", target, level, args);
+}
+
+// logs go to stderr so they never mix with answers on stdout
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled(module_path!(), $level) {
+            $crate::log::write(module_path!(), $level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Debug, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Trace, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{enabled, level, set_level, slot, Level};
+
+    #[test]
+    fn test_slot() {
+        assert_eq!(slot("day_16"), 16);
+        assert_eq!(slot("day_08::decoder"), 8);
+        assert_eq!(slot("utils::log"), 0);
+        assert_eq!(slot("day_99"), 0);
+    }
+
+    #[test]
+    fn test_per_day_level() {
+        set_level(3, Level::Debug);
+
+        assert_eq!(level(3), Level::Debug);
+        assert!(enabled("day_03", Level::Debug));
+        assert!(!enabled("day_03", Level::Trace));
+        assert!(!enabled("day_04", Level::Debug));
+    }
+}
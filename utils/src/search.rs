@@ -0,0 +1,202 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Every search starts at `start`, expands nodes with `successors` and stops at the
+// first node `success` accepts, returning the cost to get there and the path from
+// `start` to it, both ends included; `None` if no such node is reachable.
+
+// the nodes seen so far, with the cheapest known way to reach each of them
+struct Visited<N, C> {
+    index: HashMap<N, usize>,
+    // node, index of the node it was reached from, cost
+    nodes: Vec<(N, usize, C)>,
+}
+
+impl<N: Eq + Hash + Clone, C: Ord + Copy> Visited<N, C> {
+    fn new(start: &N, cost: C) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            nodes: vec![(start.clone(), usize::MAX, cost)],
+        }
+    }
+
+    // the index of `node` if it's new or `cost` beats what was known about it
+    fn improve(&mut self, node: N, parent: usize, cost: C) -> Option<usize> {
+        match self.index.get(&node) {
+            Some(&i) if self.nodes[i].2 <= cost => None,
+            Some(&i) => {
+                self.nodes[i] = (node, parent, cost);
+                Some(i)
+            }
+            None => {
+                self.index.insert(node.clone(), self.nodes.len());
+                self.nodes.push((node, parent, cost));
+                Some(self.nodes.len() - 1)
+            }
+        }
+    }
+
+    fn path(&self, mut i: usize) -> (C, Vec<N>) {
+        let cost = self.nodes[i].2;
+        let mut path = Vec::new();
+        while i != usize::MAX {
+            path.push(self.nodes[i].0.clone());
+            i = self.nodes[i].1;
+        }
+        path.reverse();
+        (cost, path)
+    }
+}
+
+// every edge costs 1
+pub fn bfs<N, FN, IN, FS>(start: N, mut successors: FN, mut success: FS) -> Option<(usize, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new(&start, 0);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        let (node, _, cost) = visited.nodes[i].clone();
+        if success(&node) {
+            return Some(visited.path(i));
+        }
+
+        for next in successors(&node) {
+            queue.extend(visited.improve(next, i, cost + 1));
+        }
+    }
+
+    None
+}
+
+pub fn dijkstra<N, C, FN, IN, FS>(start: N, successors: FN, success: FS) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+// `heuristic` must never overestimate the cost left to reach a goal
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new(&start, C::default());
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // a cheaper way to the node was found after this one was queued
+        if cost > visited.nodes[i].2 {
+            continue;
+        }
+        let node = visited.nodes[i].0.clone();
+        if success(&node) {
+            return Some(visited.path(i));
+        }
+
+        for (next, weight) in successors(&node) {
+            let estimate = heuristic(&next);
+            if let Some(j) = visited.improve(next, i, cost + weight) {
+                heap.push(Reverse((cost + weight + estimate, cost + weight, j)));
+            }
+        }
+    }
+
+    None
+}
+
+// Dijkstra with a queue of one bucket per cost instead of a heap, which is faster
+// when the weights are small integers
+pub fn dijkstra_buckets<N, FN, IN, FS>(
+    start: N,
+    mut successors: FN,
+    mut success: FS,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, usize)>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut visited = Visited::new(&start, 0);
+    let mut buckets = vec![vec![0]];
+    let mut queued = 1;
+
+    let mut cost = 0;
+    while queued > 0 {
+        while let Some(i) = buckets[cost].pop() {
+            queued -= 1;
+            if cost > visited.nodes[i].2 {
+                continue;
+            }
+            let node = visited.nodes[i].0.clone();
+            if success(&node) {
+                return Some(visited.path(i));
+            }
+
+            for (next, weight) in successors(&node) {
+                if let Some(j) = visited.improve(next, i, cost + weight) {
+                    if buckets.len() <= cost + weight {
+                        buckets.resize_with(cost + weight + 1, Vec::new);
+                    }
+                    buckets[cost + weight].push(j);
+                    queued += 1;
+                }
+            }
+        }
+        cost += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dijkstra, dijkstra_buckets};
+
+    // 0 -> 1 costs 1, then 1 -> 3 costs 5, while 0 -> 2 -> 3 costs 2 + 2
+    fn successors(&node: &u32) -> Vec<(u32, usize)> {
+        match node {
+            0 => vec![(1, 1), (2, 2)],
+            1 => vec![(3, 5)],
+            2 => vec![(3, 2), (0, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let plain = |n: &u32| successors(n).into_iter().map(|(n, _)| n);
+        assert_eq!(bfs(0, plain, |&n| n == 3), Some((2, vec![0, 1, 3])));
+        assert_eq!(bfs(0, plain, |&n| n == 4), None);
+
+        let expected = Some((4, vec![0, 2, 3]));
+        assert_eq!(dijkstra(0, successors, |&n| n == 3), expected);
+        assert_eq!(dijkstra_buckets(0, successors, |&n| n == 3), expected);
+        assert_eq!(
+            astar(0, successors, |&n| 3 - n as usize, |&n| n == 3),
+            expected
+        );
+        assert_eq!(dijkstra(0, successors, |&n| n == 0), Some((0, vec![0])));
+    }
+}
@@ -0,0 +1,120 @@
+use std::any::Any;
+
+use crate::{Answer, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Self::A => "a",
+            Self::B => "b",
+        })
+    }
+}
+
+// a named command `aoc2021 repl <day>` can run against the parsed input, eg to
+// show or step through intermediate state; `args` is the rest of the line
+pub struct Command<I: 'static> {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub run: fn(input: &mut I, args: &str) -> Result<String, String>,
+}
+
+pub trait Solution {
+    type Input: 'static;
+
+    const DAY: u8;
+    const NAME: &'static str;
+    const INPUT: &'static str;
+
+    const COMMANDS: &'static [Command<Self::Input>] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_a(input: &Self::Input) -> Answer;
+    fn part_b(input: &Self::Input) -> Answer;
+}
+
+// parses `input`, or the day's own input without one, and solves `part` of it
+pub fn solve<S: Solution>(part: Part, input: Option<&str>) -> Result<Answer, ParseError> {
+    let input = S::parse(input.unwrap_or(S::INPUT))?;
+    Ok(match part {
+        Part::A => S::part_a(&input),
+        Part::B => S::part_b(&input),
+    })
+}
+
+// object safe counterpart of `Solution`, so days with different input types
+// can live in the same registry
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn input(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn solve(&self, part: Part, input: &dyn Any) -> Answer;
+    // name and help of every repl command
+    fn commands(&self) -> Vec<(&'static str, &'static str)>;
+    // `None` if the day has no command called `name`
+    fn command(
+        &self,
+        name: &str,
+        input: &mut dyn Any,
+        args: &str,
+    ) -> Option<Result<String, String>>;
+
+    fn run(&self, part: Part, input: Option<&str>) -> Result<Answer, ParseError> {
+        let parsed = self.parse(input.unwrap_or_else(|| self.input()))?;
+        Ok(self.solve(part, parsed.as_ref()))
+    }
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> Answer {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by another puzzle");
+
+        match part {
+            Part::A => S::part_a(input),
+            Part::B => S::part_b(input),
+        }
+    }
+
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        S::COMMANDS.iter().map(|c| (c.name, c.help)).collect()
+    }
+
+    fn command(
+        &self,
+        name: &str,
+        input: &mut dyn Any,
+        args: &str,
+    ) -> Option<Result<String, String>> {
+        let input = input
+            .downcast_mut::<S::Input>()
+            .expect("input was parsed by another puzzle");
+
+        let command = S::COMMANDS.iter().find(|c| c.name == name)?;
+        Some((command.run)(input, args))
+    }
+}
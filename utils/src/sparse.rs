@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Grid, Neighbors};
+
+type Point = (i64, i64);
+
+// an unbounded grid indexed by signed (row, col), where every cell that was never set
+// holds `background`; only the cells that differ from it are stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    background: T,
+    bbox: Option<(Point, Point)>,
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    pub fn new(background: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            background,
+            bbox: None,
+        }
+    }
+
+    // the grid's (0, 0) ends up at (0, 0)
+    pub fn from_grid(grid: &Grid<T>, background: T) -> Self {
+        let mut sparse = Self::new(background);
+        for ((i, j), value) in grid.indexed_iter() {
+            sparse.set((i as i64, j as i64), value.clone());
+        }
+        sparse
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.background)
+    }
+
+    pub fn set(&mut self, p: Point, value: T) {
+        if value == self.background {
+            self.cells.remove(&p);
+            return;
+        }
+
+        self.bbox = Some(match self.bbox {
+            None => (p, p),
+            Some((min, max)) => (
+                (min.0.min(p.0), min.1.min(p.1)),
+                (max.0.max(p.0), max.1.max(p.1)),
+            ),
+        });
+        self.cells.insert(p, value);
+    }
+
+    // the inclusive corners of a box holding every cell that differs from the
+    // background; it grows as cells are set but never shrinks
+    pub fn bbox(&self) -> Option<(Point, Point)> {
+        self.bbox
+    }
+
+    // the number of cells that differ from the background
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // the cells that differ from the background, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    pub fn neighbors(&self, (i, j): Point, neighbors: Neighbors) -> impl Iterator<Item = Point> {
+        neighbors
+            .offsets()
+            .iter()
+            .map(move |&(di, dj)| (i + di as i64, j + dj as i64))
+    }
+
+    pub fn neighbor_values(
+        &self,
+        p: Point,
+        neighbors: Neighbors,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(p, neighbors).map(move |p| (p, self.get(p)))
+    }
+
+    // the bounding box as a dense grid, its (0, 0) being the top left corner
+    pub fn to_grid(&self) -> Grid<T> {
+        match self.bbox {
+            Some((origin, _)) => self.to_grid_from(origin),
+            None => Grid::from_vec(Vec::new()),
+        }
+    }
+
+    // a dense grid from `origin` to the bottom right corner of the bounding box, with
+    // `origin` at its (0, 0); cells above or left of it are left out
+    pub fn to_grid_from(&self, (i0, j0): Point) -> Grid<T> {
+        let (i1, j1) = match self.bbox {
+            Some((_, max)) => max,
+            None => return Grid::from_vec(Vec::new()),
+        };
+
+        let rows = (i0..=i1)
+            .map(|i| (j0..=j1).map(|j| self.get((i, j)).clone()).collect())
+            .collect();
+        Grid::from_vec(rows)
+    }
+}
+
+impl<T: Clone + PartialEq + fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_grid().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseGrid;
+    use crate::Neighbors;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new('.');
+        grid.set((-1, 2), '#');
+        grid.set((1, 0), '#');
+        grid.set((5, 5), '.');
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bbox(), Some(((-1, 0), (1, 2))));
+        assert_eq!(grid.get((100, -100)), &'.');
+        assert_eq!(
+            grid.neighbor_values((0, 1), Neighbors::Diagonal)
+                .filter(|(_, &c)| c == '#')
+                .count(),
+            2
+        );
+        assert_eq!(grid.to_string(), "..#\n...\n#..");
+        assert_eq!(
+            grid.to_grid_from((-2, -1)).to_string(),
+            "....\n...#\n....\n.#.."
+        );
+    }
+}